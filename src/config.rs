@@ -0,0 +1,456 @@
+use anyhow::{bail, Context, Result};
+use glob::Pattern;
+use serde::Deserialize;
+use std::{collections::BTreeMap, fs, path::Path, path::PathBuf};
+use toml::Value;
+use url::Url;
+
+use crate::{
+    models::test_report::{PrettyPrint, Report, ReportBuilder},
+    quality_gate::FailOn,
+    reporters::{
+        badge::BadgeReport,
+        email::EmailReport,
+        jira::JiraReport,
+        prometheus::PrometheusReport,
+        registry::{Reporter, ReporterRegistry},
+        telegram::TelegramReport,
+        webex::WebexReport,
+        zulip::ZulipReport,
+    },
+};
+
+/// Name of the configuration file looked for in the working directory
+pub const CONFIG_FILE_NAME: &str = "testvox.toml";
+
+/// Project configuration, usually read from a `testvox.toml` file. String values can refer to
+/// environment variables as `${NAME}`, or `${NAME:-default}` to fall back to a default value when
+/// the variable is not set. `$$` yields a literal `$`.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// the title of the report
+    pub title: Option<String>,
+    /// the test report patterns to look for
+    pub reports_pattern: Option<Vec<String>>,
+    /// whether to include passed tests in the report
+    pub include_passed: bool,
+    /// whether to include skipped tests in the report
+    pub include_skipped: bool,
+    /// optional link to view more details related to the report, usually a CI workflow
+    pub link: Option<Url>,
+    /// the format of the report printed to the standard output
+    pub format: Option<String>,
+    /// additional reports written to files, by format
+    pub outputs: BTreeMap<String, PathBuf>,
+    /// optional upper bound of the number of reported test results
+    pub max_test_results: Option<usize>,
+    /// optional upper bound of the length of failure messages, in characters
+    pub max_failure_message_length: Option<usize>,
+    /// patterns of the test names, or suite names, to keep. All tests are kept when empty
+    pub include_tests: Vec<String>,
+    /// patterns of the test names, or suite names, to leave out
+    pub exclude_tests: Vec<String>,
+    /// whether to fail on test results files that can't be read or parsed, rather than warning about them
+    pub strict: bool,
+    /// conditions that fail the quality gate, after the report is printed
+    pub fail_on: Vec<FailOn>,
+    /// settings of the reporters that have some
+    pub reporters: ReportersConfig,
+}
+
+/// Settings of the reporters, by the name they are registered with
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ReportersConfig {
+    pub badge: BadgeConfig,
+    pub email: EmailConfig,
+    pub jira: JiraConfig,
+    pub prometheus: PrometheusConfig,
+    pub telegram: TelegramConfig,
+    pub webex: WebexConfig,
+    pub zulip: ZulipConfig,
+}
+
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct BadgeConfig {
+    /// the text on the left side of the badge
+    pub label: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct EmailConfig {
+    /// the template of the subject, see `email::DEFAULT_SUBJECT_TEMPLATE`
+    pub subject_template: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct JiraConfig {
+    /// the key of the project issues are created in
+    pub project_key: Option<String>,
+    /// the type of the created issues
+    pub issue_type: Option<String>,
+    /// additional labels of the created issues
+    pub labels: Vec<String>,
+}
+
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct PrometheusConfig {
+    /// maximum number of per-test duration gauges, disabled when not given
+    pub test_durations: Option<usize>,
+}
+
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct TelegramConfig {
+    /// the chat the message is sent to
+    pub chat_id: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct WebexConfig {
+    /// the room the message is posted to
+    pub room_id: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ZulipConfig {
+    /// the stream the message is sent to
+    pub stream: Option<String>,
+    /// the topic of the message
+    pub topic: Option<String>,
+}
+
+impl Config {
+    /// Reads the configuration file at the given path, interpolating environment variables
+    pub fn load(path: &Path) -> Result<Config> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Unable to read {}", path.display()))?;
+
+        Config::parse(&contents, |name| std::env::var(name).ok())
+            .with_context(|| format!("Invalid configuration file {}", path.display()))
+    }
+
+    /// Parses the given configuration, interpolating variables with the given lookup
+    pub fn parse<F>(contents: &str, lookup: F) -> Result<Config>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let mut value: Value = toml::from_str(contents)?;
+        interpolate_value(&mut value, &lookup)?;
+
+        Ok(value.try_into()?)
+    }
+
+    /// Applies the name filters to the given builder
+    pub fn apply_filters(&self, mut builder: ReportBuilder) -> Result<ReportBuilder> {
+        for pattern in self.include_tests.iter() {
+            builder = builder.include_tests_matching(
+                Pattern::new(pattern).with_context(|| format!("Invalid pattern '{}'", pattern))?,
+            );
+        }
+        for pattern in self.exclude_tests.iter() {
+            builder = builder.exclude_tests_matching(
+                Pattern::new(pattern).with_context(|| format!("Invalid pattern '{}'", pattern))?,
+            );
+        }
+
+        Ok(builder)
+    }
+
+    /// Applies the name filters and the truncation limits to the given builder
+    pub fn apply(&self, builder: ReportBuilder) -> Result<ReportBuilder> {
+        let mut builder = self.apply_filters(builder)?;
+
+        if let Some(max_test_results) = self.max_test_results {
+            builder = builder.with_max_test_results(max_test_results);
+        }
+        if let Some(max_length) = self.max_failure_message_length {
+            builder = builder.with_max_failure_message_length(max_length);
+        }
+
+        Ok(builder)
+    }
+
+    /// Yields the default registry, with the reporters set up according to their settings
+    pub fn registry(&self) -> ReporterRegistry {
+        let mut registry = ReporterRegistry::default();
+        let reporters = &self.reporters;
+
+        if let Some(label) = reporters.badge.label.clone() {
+            registry.register(
+                "badge",
+                configured(move |r: BadgeReport| r.with_label(label.clone())),
+            );
+        }
+
+        if let Some(subject_template) = reporters.email.subject_template.clone() {
+            registry.register(
+                "email",
+                configured(move |r: EmailReport| r.with_subject_template(subject_template.clone())),
+            );
+        }
+
+        if let Some(project_key) = reporters.jira.project_key.clone() {
+            let (issue_type, labels) = (
+                reporters.jira.issue_type.clone(),
+                reporters.jira.labels.clone(),
+            );
+            registry.register(
+                "jira",
+                configured(move |mut r: JiraReport| {
+                    r = r.with_project_key(project_key.clone());
+                    if let Some(issue_type) = &issue_type {
+                        r = r.with_issue_type(issue_type.clone());
+                    }
+                    for label in labels.iter() {
+                        r = r.with_label(label.clone());
+                    }
+                    r
+                }),
+            );
+        }
+
+        if let Some(limit) = reporters.prometheus.test_durations {
+            registry.register(
+                "prometheus",
+                configured(move |r: PrometheusReport| r.with_test_durations(limit)),
+            );
+        }
+
+        if let Some(chat_id) = reporters.telegram.chat_id.clone() {
+            registry.register(
+                "telegram",
+                configured(move |r: TelegramReport| r.with_chat_id(chat_id.clone())),
+            );
+        }
+
+        if let Some(room_id) = reporters.webex.room_id.clone() {
+            registry.register(
+                "webex",
+                configured(move |r: WebexReport| r.with_room_id(room_id.clone())),
+            );
+        }
+
+        let zulip = (
+            reporters.zulip.stream.clone(),
+            reporters.zulip.topic.clone(),
+        );
+        registry.register(
+            "zulip",
+            configured(move |mut r: ZulipReport| {
+                let (stream, topic) = &zulip;
+                if let Some(stream) = stream {
+                    r = r.with_stream(stream.clone());
+                }
+                if let Some(topic) = topic {
+                    r = r.with_topic(topic.clone());
+                }
+                r
+            }),
+        );
+
+        registry
+    }
+}
+
+/// Yields a reporter rendering the report type `T`, set up by the given function
+fn configured<T, F>(configure: F) -> impl Reporter
+where
+    T: From<ReportBuilder> + PrettyPrint,
+    F: Fn(T) -> T + Send + Sync,
+{
+    move |report: &Report| {
+        Ok(configure(T::from(ReportBuilder::from(report)))
+            .to_string_pretty()
+            .into_bytes())
+    }
+}
+
+fn interpolate_value<F>(value: &mut Value, lookup: &F) -> Result<()>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    match value {
+        Value::String(text) => *text = interpolate(text, lookup)?,
+        Value::Array(values) => {
+            for value in values.iter_mut() {
+                interpolate_value(value, lookup)?;
+            }
+        }
+        Value::Table(table) => {
+            for (_, value) in table.iter_mut() {
+                interpolate_value(value, lookup)?;
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Replaces the `${NAME}` and `${NAME:-default}` references in the given text
+fn interpolate<F>(text: &str, lookup: &F) -> Result<String>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let mut interpolated = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('$') {
+        interpolated.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        if let Some(after) = rest.strip_prefix("$$") {
+            interpolated.push('$');
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix("${") {
+            let Some(end) = after.find('}') else {
+                bail!("Unterminated variable reference in '{}'", text);
+            };
+            let (name, default) = match after[..end].split_once(":-") {
+                Some((name, default)) => (name, Some(default)),
+                None => (&after[..end], None),
+            };
+            match (lookup(name), default) {
+                (Some(value), _) => interpolated.push_str(&value),
+                (None, Some(default)) => interpolated.push_str(default),
+                (None, None) => bail!("Environment variable {} is not set", name),
+            }
+            rest = &after[end + 1..];
+        } else {
+            interpolated.push('$');
+            rest = &rest[1..];
+        }
+    }
+    interpolated.push_str(rest);
+
+    Ok(interpolated)
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use std::path::PathBuf;
+
+    use crate::models::{
+        test_report::{Report, ReportBuilder},
+        test_result::TestResult,
+        test_status::TestStatus,
+    };
+
+    use super::{interpolate, Config, FailOn};
+
+    fn lookup(name: &str) -> Option<String> {
+        match name {
+            "BRANCH" => Some("main".to_string()),
+            "CI_JOB_URL" => Some("http://localhost/run/123".to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn should_parse_a_configuration_file() {
+        let config = Config::parse(
+            indoc! {r#"
+                title = "Nightly tests on ${BRANCH}"
+                reports_pattern = ["build/**/*.xml"]
+                include_skipped = true
+                link = "${CI_JOB_URL}"
+                format = "gitlab-note"
+                max_test_results = 20
+                exclude_tests = ["*flaky*"]
+                fail_on = ["failures-above=3", "no-tests"]
+
+                [outputs]
+                junit = "merged.xml"
+
+                [reporters.badge]
+                label = "e2e"
+            "#},
+            lookup,
+        )
+        .expect("unable to parse configuration");
+
+        assert_eq!(config.title.as_deref(), Some("Nightly tests on main"));
+        assert_eq!(
+            config.reports_pattern,
+            Some(vec!["build/**/*.xml".to_string()])
+        );
+        assert!(config.include_skipped);
+        assert!(!config.include_passed);
+        assert_eq!(config.link.unwrap().as_str(), "http://localhost/run/123");
+        assert_eq!(config.format.as_deref(), Some("gitlab-note"));
+        assert_eq!(config.max_test_results, Some(20));
+        assert_eq!(config.exclude_tests, vec!["*flaky*".to_string()]);
+        assert_eq!(
+            config.fail_on,
+            vec![FailOn::FailuresAbove(3), FailOn::NoTests]
+        );
+        assert_eq!(
+            config.outputs.get("junit"),
+            Some(&PathBuf::from("merged.xml"))
+        );
+        assert_eq!(config.reporters.badge.label.as_deref(), Some("e2e"));
+    }
+
+    #[test]
+    fn should_reject_unknown_settings() {
+        let error = Config::parse("titel = \"typo\"", lookup).expect_err("parse should fail");
+
+        assert!(error.to_string().contains("unknown field `titel`"));
+    }
+
+    #[test]
+    fn should_interpolate_environment_variables() {
+        assert_eq!(
+            interpolate("${BRANCH}/${MISSING:-none} costs $$5 or $5", &lookup).unwrap(),
+            "main/none costs $5 or $5"
+        );
+        assert_eq!(
+            interpolate("${MISSING}", &lookup)
+                .expect_err("interpolate should fail")
+                .to_string(),
+            "Environment variable MISSING is not set"
+        );
+        assert!(interpolate("${BRANCH", &lookup).is_err());
+    }
+
+    #[test]
+    fn should_set_up_reporters_according_to_their_settings() {
+        let config = Config::parse(
+            indoc! {r#"
+                [reporters.badge]
+                label = "e2e"
+
+                [reporters.jira]
+                project_key = "QA"
+            "#},
+            lookup,
+        )
+        .unwrap();
+        let report = ReportBuilder::new()
+            .with_test_results(vec![TestResult::builder()
+                .with_name("a test failed".to_string())
+                .with_status(TestStatus::Failed)
+                .build()])
+            .build::<Report>();
+
+        let badge = config.registry().render("badge", &report).unwrap();
+
+        assert!(String::from_utf8(badge).unwrap().contains(">e2e<"));
+
+        let jira = config.registry().render("jira", &report).unwrap();
+        assert!(String::from_utf8(jira).unwrap().contains("\"key\": \"QA\""));
+        assert!(Config::default()
+            .registry()
+            .render("jira", &report)
+            .is_err());
+    }
+}
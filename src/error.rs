@@ -0,0 +1,36 @@
+use std::{io, path::PathBuf};
+
+use thiserror::Error;
+
+/// Errors yielded when looking for, reading and parsing test results
+#[derive(Debug, Error)]
+pub enum TestvoxError {
+    /// the glob pattern is invalid
+    #[error("invalid file pattern '{pattern}': {message}")]
+    InvalidPattern { pattern: String, message: String },
+    /// no file matches the glob patterns
+    #[error("no test results file matches {}", .patterns.join(", "))]
+    NoFilesMatched { patterns: Vec<String> },
+    /// the file can't be read
+    #[error("unable to read {}: {error}", .path.display())]
+    UnreadableFile { path: PathBuf, error: io::Error },
+    /// the test results are not well-formed XML
+    #[error("malformed XML at line {line}, column {column}: {message}")]
+    MalformedXml {
+        line: u32,
+        column: u32,
+        message: String,
+    },
+    /// the test results are in none of the supported formats
+    #[error("unknown test results format, expected JUnit XML, go test JSON or libtest JSON")]
+    UnknownFormat,
+    /// the parser was unable to parse the test results
+    #[error("unable to parse test results: {0}")]
+    Parse(String),
+    /// the test results of the file can't be parsed
+    #[error("{}: {error}", .path.display())]
+    InFile {
+        path: PathBuf,
+        error: Box<TestvoxError>,
+    },
+}
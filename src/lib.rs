@@ -1,39 +1,54 @@
 //!
 //! Testvox helps you turning test reports into human readable messages, ready to be shared on common messaging apps.
 
-use anyhow::Result;
+use error::TestvoxError;
+use glob::glob;
 use models::{
     test_report::{PrettyPrint, ReportBuilder},
     test_result::TestResult,
 };
-use parsers::{junit::JunitTestParser, TestParser};
+use parsers::{gotest::GoTestParser, junit::JunitTestParser, libtest::LibtestParser, TestParser};
+use std::{
+    collections::HashSet,
+    fs, io,
+    path::{Path, PathBuf},
+};
 use url::Url;
 
+/// project configuration, usually read from testvox.toml files
+pub mod config;
+/// errors yielded when looking for, reading and parsing test results
+pub mod error;
 /// basic models of the library
 pub mod models;
 /// generic and custom parsers types
 pub mod parsers;
+/// conditions that fail quality gates, with distinct exit codes
+pub mod quality_gate;
 /// generic and custom reporter types
 pub mod reporters;
+/// logic to deliver reports over the network, enabled by cargo features
+pub mod senders;
 
 /// Utility to create a test report of the desired format. The generic type `T` must implement traits
 /// that hold the logic of how the test results in specific formats should be formatted, and pretty printed.
-pub fn create_test_report<T>(request: CreateTestReportRequest) -> T
+pub fn create_test_report<T>(request: CreateTestReportRequest) -> Result<T, TestvoxError>
 where
     T: From<ReportBuilder> + PrettyPrint,
 {
+    Ok(create_report_builder(request)?.build::<T>())
+}
+
+/// Utility to parse the test results of a request into a report builder, that can be cloned to build
+/// reports of several formats out of a single parse. Fails on the first contents that can't be parsed.
+pub fn create_report_builder(
+    request: CreateTestReportRequest,
+) -> Result<ReportBuilder, TestvoxError> {
     // Automatically detect test parser and flatten all results into a single array of results
-    let test_results: Vec<TestResult> = request
-        .reports_contents
-        .into_iter()
-        .map(|test_file| {
-            detect_parser(test_file)
-                .expect("Unable to detect test parser")
-                .parse()
-        })
-        .filter_map(|test_results| test_results.ok())
-        .flatten()
-        .collect();
+    let mut test_results = request.test_results;
+    for report_content in request.reports_contents {
+        test_results.extend(parse_test_results(report_content)?);
+    }
 
     let mut report_builder = ReportBuilder::new()
         .with_title(request.title)
@@ -51,16 +66,145 @@ where
         report_builder = report_builder.with_link(link);
     }
 
-    report_builder.build::<T>()
+    Ok(report_builder)
 }
 
-fn detect_parser(report_content: String) -> Result<Box<dyn TestParser>> {
-    //TODO: implement real test detector
-    Ok(Box::new(JunitTestParser::from(report_content)))
+/// Utility to parse the contents of a single test results file, with the parser detected out of them.
+pub fn parse_test_results(report_content: String) -> Result<Vec<TestResult>, TestvoxError> {
+    detect_parser(report_content)?
+        .parse()
+        .map_err(|e| match e.downcast_ref::<roxmltree::Error>() {
+            None if e.is::<TestvoxError>() => e
+                .downcast::<TestvoxError>()
+                .expect("the error is a TestvoxError"),
+            Some(xml_error) => {
+                let pos = xml_error.pos();
+                let message = xml_error.to_string();
+                TestvoxError::MalformedXml {
+                    line: pos.row,
+                    column: pos.col,
+                    // the position is part of the variant already
+                    message: message
+                        .strip_suffix(&format!(" at {}", pos))
+                        .unwrap_or(&message)
+                        .to_string(),
+                }
+            }
+            None => TestvoxError::Parse(format!("{:#}", e)),
+        })
+}
+
+/// Path that stands for the standard input
+pub const STDIN: &str = "-";
+
+/// Extensions of the files in the supported formats, looked for when scanning directories
+const KNOWN_EXTENSIONS: [&str; 3] = ["xml", "json", "jsonl"];
+
+/// Utility to read and parse a single test results file, or the standard input when the path is `-`.
+/// Errors refer to the file.
+pub fn read_test_results_file(path: &Path) -> Result<Vec<TestResult>, TestvoxError> {
+    let in_file = |error: TestvoxError| TestvoxError::InFile {
+        path: path.to_path_buf(),
+        error: Box::new(error),
+    };
+    let unreadable = |error: io::Error| TestvoxError::UnreadableFile {
+        path: path.to_path_buf(),
+        error,
+    };
+
+    let report_content = match path == Path::new(STDIN) {
+        true => io::read_to_string(io::stdin()).map_err(unreadable)?,
+        false => fs::read_to_string(path).map_err(unreadable)?,
+    };
+
+    parse_test_results(report_content).map_err(in_file)
+}
+
+/// Utility to find the test results files given as glob patterns, or paths of files or directories.
+/// Directories are scanned recursively for files in the supported formats, and `-` stands for the
+/// standard input, to be read with `read_test_results_file`.
+pub fn find_test_results_files(patterns: &[String]) -> Result<Vec<PathBuf>, TestvoxError> {
+    let mut paths: Vec<PathBuf> = vec![];
+    for pattern in patterns {
+        // existing paths are taken as they are, as they may contain glob special characters
+        if pattern == STDIN || Path::new(pattern).exists() {
+            add_test_results_files(PathBuf::from(pattern), &mut paths);
+            continue;
+        }
+
+        let matches = glob(pattern).map_err(|e| TestvoxError::InvalidPattern {
+            pattern: pattern.clone(),
+            message: e.to_string(),
+        })?;
+        for path in matches.filter_map(|path| path.ok()) {
+            add_test_results_files(path, &mut paths);
+        }
+    }
+
+    // files matched several times, such as the standard input given twice, are read once
+    let mut seen: HashSet<PathBuf> = HashSet::new();
+    paths.retain(|path| seen.insert(path.clone()));
+
+    if paths.is_empty() {
+        return Err(TestvoxError::NoFilesMatched {
+            patterns: patterns.to_vec(),
+        });
+    }
+
+    Ok(paths)
+}
+
+fn add_test_results_files(path: PathBuf, paths: &mut Vec<PathBuf>) {
+    if !path.is_dir() {
+        paths.push(path);
+        return;
+    }
+
+    let Ok(entries) = fs::read_dir(&path) else {
+        return;
+    };
+    let mut entries: Vec<PathBuf> = entries.filter_map(|e| e.ok()).map(|e| e.path()).collect();
+    entries.sort();
+
+    for entry in entries {
+        let known_format = entry
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| KNOWN_EXTENSIONS.contains(&e));
+        if entry.is_dir() || known_format {
+            add_test_results_files(entry, paths);
+        }
+    }
+}
+
+fn detect_parser(report_content: String) -> Result<Box<dyn TestParser>, TestvoxError> {
+    // files may be saved with a byte order mark
+    let report_content = report_content
+        .strip_prefix('\u{feff}')
+        .map(str::to_string)
+        .unwrap_or(report_content);
+    if report_content.trim_start().starts_with('<') {
+        return Ok(Box::new(JunitTestParser::from(report_content)));
+    }
+
+    // JSON formats are streams of events, one per line, told apart by the fields of the first one
+    let first_event = report_content
+        .lines()
+        .find(|l| l.trim_start().starts_with('{'))
+        .and_then(|l| serde_json::from_str::<serde_json::Value>(l).ok());
+    match first_event {
+        Some(event) if event.get("Action").is_some() => {
+            Ok(Box::new(GoTestParser::from(report_content)))
+        }
+        Some(event) if event.get("type").is_some() && event.get("event").is_some() => {
+            Ok(Box::new(LibtestParser::from(report_content)))
+        }
+        _ => Err(TestvoxError::UnknownFormat),
+    }
 }
 
 /// A struct that describe the request for creating a report
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct CreateTestReportRequest {
     /// the title that the generated report should have
     pub title: String,
@@ -72,19 +216,158 @@ pub struct CreateTestReportRequest {
     pub include_skipped: bool,
     /// optional url to external system, usually a CI pipeline
     pub link: Option<Url>,
+    /// test results parsed beforehand, reported along with the ones parsed out of `reports_contents`
+    pub test_results: Vec<TestResult>,
 }
 
 #[cfg(test)]
 mod tests {
     use assert_json::assert_json;
-    use std::fs;
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+    };
     use url::Url;
 
     use crate::{
-        create_test_report, models::test_report::PrettyPrint, reporters::slack::SlackReport,
-        CreateTestReportRequest,
+        create_test_report, error::TestvoxError, find_test_results_files,
+        models::test_report::PrettyPrint, parse_test_results, read_test_results_file,
+        reporters::slack::SlackReport, CreateTestReportRequest,
     };
 
+    #[test]
+    fn should_parse_test_results_of_a_single_file() {
+        let test_results = parse_test_results(
+            fs::read_to_string("./test-data/junit.xml").expect("Unable to read file"),
+        )
+        .expect("Unable to parse test results");
+
+        assert_eq!(test_results.len(), 5);
+    }
+
+    #[test]
+    fn should_fail_to_parse_malformed_or_unknown_test_results() {
+        let error = parse_test_results("<testsuites>\n  <testcase/>\n</testsuite>".to_string())
+            .expect_err("parse should fail");
+        assert_eq!(
+            error.to_string(),
+            "malformed XML at line 3, column 1: expected 'testsuites' tag, not 'testsuite'"
+        );
+        assert!(matches!(
+            error,
+            TestvoxError::MalformedXml {
+                line: 3,
+                column: 1,
+                ..
+            }
+        ));
+
+        let error =
+            parse_test_results("{\"tests\": []}".to_string()).expect_err("parse should fail");
+        assert!(matches!(error, TestvoxError::UnknownFormat));
+    }
+
+    #[test]
+    fn should_parse_test_results_starting_with_a_byte_order_mark() {
+        let contents = fs::read_to_string("./test-data/junit.xml").expect("Unable to read file");
+
+        let test_results = parse_test_results(format!("\u{feff}{}", contents))
+            .expect("Unable to parse test results");
+
+        assert_eq!(test_results.len(), 5);
+    }
+
+    #[test]
+    fn should_fail_to_parse_xml_documents_other_than_junit() {
+        let error = parse_test_results("<project><foo/></project>".to_string())
+            .expect_err("parse should fail");
+
+        assert!(matches!(error, TestvoxError::UnknownFormat));
+    }
+
+    #[test]
+    fn should_refer_to_files_in_errors() {
+        let error = read_test_results_file(Path::new("./test-data/missing.xml"))
+            .expect_err("read should fail");
+        assert!(matches!(error, TestvoxError::UnreadableFile { .. }));
+
+        let error = find_test_results_files(&["./test-data/*.json".to_string()])
+            .expect_err("find should fail");
+        assert_eq!(
+            error.to_string(),
+            "no test results file matches ./test-data/*.json"
+        );
+
+        let error = find_test_results_files(&["./[".to_string()]).expect_err("find should fail");
+        assert!(matches!(error, TestvoxError::InvalidPattern { .. }));
+    }
+
+    #[test]
+    fn should_find_files_by_path_and_scan_directories() {
+        let dir = std::env::temp_dir().join(format!("testvox-{}", std::process::id()));
+        fs::create_dir_all(dir.join("nested/deeper")).expect("Unable to create directories");
+        for file in [
+            "a.xml",
+            "notes.txt",
+            "nested/deeper/b.xml",
+            "[c].xml",
+            "go.json",
+        ] {
+            fs::write(dir.join(file), "<testsuites/>").expect("Unable to write file");
+        }
+
+        let scanned =
+            find_test_results_files(&[dir.display().to_string()]).expect("Unable to find files");
+        let literal = find_test_results_files(&[
+            dir.join("[c].xml").display().to_string(),
+            "-".to_string(),
+            dir.join("*.xml").display().to_string(),
+            "-".to_string(),
+        ])
+        .expect("Unable to find files");
+        fs::remove_dir_all(&dir).expect("Unable to remove directories");
+
+        assert_eq!(
+            scanned,
+            vec![
+                dir.join("[c].xml"),
+                dir.join("a.xml"),
+                dir.join("go.json"),
+                dir.join("nested/deeper/b.xml")
+            ]
+        );
+        assert_eq!(
+            literal,
+            vec![dir.join("[c].xml"), PathBuf::from("-"), dir.join("a.xml")]
+        );
+    }
+
+    #[test]
+    fn should_parse_go_test_and_libtest_events() {
+        let go_test = parse_test_results(
+            "{\"Action\":\"pass\",\"Package\":\"example.com/auth\",\"Test\":\"TestLogin\"}\n"
+                .to_string(),
+        )
+        .expect("Unable to parse test results");
+        assert_eq!(go_test[0].name, "TestLogin");
+
+        let libtest = parse_test_results(
+            "{ \"type\": \"test\", \"event\": \"ok\", \"name\": \"tests::a\" }\n".to_string(),
+        )
+        .expect("Unable to parse test results");
+        assert_eq!(libtest[0].name, "tests::a");
+    }
+
+    #[test]
+    fn should_fail_to_create_a_report_from_malformed_test_results() {
+        let req = CreateTestReportRequest {
+            reports_contents: vec!["<testsuites>".to_string()],
+            ..Default::default()
+        };
+
+        assert!(create_test_report::<SlackReport>(req).is_err());
+    }
+
     #[test]
     fn should_create_a_slack_report_from_junit_results() {
         let req = CreateTestReportRequest {
@@ -95,9 +378,10 @@ mod tests {
             include_passed: true,
             include_skipped: true,
             link: Some(Url::parse("http://localhost/run/123").expect("unable to parse link")),
+            test_results: vec![],
         };
 
-        let report: SlackReport = create_test_report(req);
+        let report: SlackReport = create_test_report(req).expect("unable to create report");
 
         assert_json!(report.to_string_pretty().as_str(), {
             "blocks": [
@@ -1,73 +1,619 @@
-use clap::Parser;
-use glob::glob;
-use std::{fs, path::PathBuf};
+use clap::{
+    builder::{PossibleValue, PossibleValuesParser},
+    Args, Parser, Subcommand,
+};
+use clap::{error::ErrorKind, CommandFactory};
+use std::{
+    ffi::OsString,
+    fs,
+    path::{Path, PathBuf},
+};
 use url::Url;
 
 use testvox::{
-    create_test_report, models::test_report::PrettyPrint, reporters::slack::SlackReport,
+    config::{Config, CONFIG_FILE_NAME},
+    create_report_builder,
+    error::TestvoxError,
+    find_test_results_files,
+    models::test_report::Report,
+    models::test_result::TestResult,
+    quality_gate::{self, FailOn},
+    read_test_results_file,
+    reporters::registry::ReporterRegistry,
     CreateTestReportRequest,
 };
 
+fn format_names() -> PossibleValuesParser {
+    PossibleValuesParser::new(
+        ReporterRegistry::default()
+            .names()
+            .into_iter()
+            .map(|name| PossibleValue::new(name.to_string())),
+    )
+}
+
+/// An additional output, written to a file
+#[derive(Clone)]
+struct Output {
+    format: String,
+    path: PathBuf,
+}
+
+fn parse_output(value: &str) -> Result<Output, String> {
+    let (format, path) = value
+        .split_once('=')
+        .ok_or("expected a format=path pair".to_string())?;
+
+    let registry = ReporterRegistry::default();
+    if registry.get(format).is_none() {
+        return Err(format!(
+            "unknown format '{}', expected one of: {}",
+            format,
+            registry.names().join(", ")
+        ));
+    }
+
+    Ok(Output {
+        format: format.to_string(),
+        path: PathBuf::from(path),
+    })
+}
+
 /// Turns test reports into human readable summaries, to be shared on common messaging apps.
 #[derive(Parser)]
 #[command(arg_required_else_help(true))]
-struct CliArgs {
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// The configuration file, whose values are overridden by the command line ones [default: testvox.toml, when found]
+    #[arg(short, long, global = true)]
+    config: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Renders the test results into a report, optionally sent over the network. Runs when options are given without a command
+    Report(Box<ReportArgs>),
+    /// Prints the counters of the test results on a single line, as `key=value` pairs
+    Summary(InputArgs),
+    /// Merges the test results into a single JUnit file
+    Merge(MergeArgs),
+    /// Converts the test results into another format, with all the tests included
+    Convert(ConvertArgs),
+    /// Checks that the test results files can be parsed, printing a diagnostic for each of them
+    Validate(InputArgs),
+}
+
+/// The test results files to read
+#[derive(Args)]
+struct InputArgs {
+    /// The test reports to read: glob patterns, paths of files or of directories scanned recursively for XML and JSON files, or `-` for the standard input [default: ./build/test-results/**/*.xml,./app/build/test-results/**/*.xml]
+    #[arg(short, long, num_args(1..), value_delimiter = ',')]
+    reports_pattern: Option<Vec<String>>,
+    /// Whether to fail on test results files that can't be read or parsed, rather than warning about them
+    #[arg(long, default_value_t = false, overrides_with = "no_strict")]
+    strict: bool,
+    /// Whether to warn about test results files that can't be read or parsed, overriding the configuration file
+    #[arg(long, default_value_t = false, overrides_with = "strict")]
+    no_strict: bool,
+}
+
+#[derive(Args)]
+struct ReportArgs {
     /// The title of the test report
-    #[arg(short, long, required = true)]
-    title: String,
-    #[arg(short = 's', long, default_value_t = false)]
+    #[arg(short, long)]
+    title: Option<String>,
+    #[arg(
+        short = 's',
+        long,
+        default_value_t = false,
+        overrides_with = "no_include_skipped"
+    )]
     /// Whether to include skipped tests in the report
     include_skipped: bool,
+    /// Whether to leave skipped tests out of the report, overriding the configuration file
+    #[arg(long, default_value_t = false, overrides_with = "include_skipped")]
+    no_include_skipped: bool,
     /// Whether to include passed tests in the report
-    #[arg(short = 'p', long, default_value_t = false)]
-    include_passed: bool,
-    /// The test report pattern to look for
     #[arg(
-        short,
+        short = 'p',
         long,
-        required = true,
-        num_args(1..),
-        value_delimiter= ',',
-        default_value = "./build/test-results/**/*.xml,./app/build/test-results/**/*.xml")
-    ]
-    reports_pattern: Vec<String>,
+        default_value_t = false,
+        overrides_with = "no_include_passed"
+    )]
+    include_passed: bool,
+    /// Whether to leave passed tests out of the report, overriding the configuration file
+    #[arg(long, default_value_t = false, overrides_with = "include_passed")]
+    no_include_passed: bool,
+    #[command(flatten)]
+    input: InputArgs,
     /// Optional link to view more details related to the report, usually a CI workflow
     #[arg(short, long, default_value = None)]
     link: Option<Url>,
+    /// The format of the report printed to the standard output [default: slack]
+    #[arg(short, long, value_parser = format_names())]
+    format: Option<String>,
+    /// Additional report written to a file, as `format=path`. Can be repeated
+    #[arg(short, long, value_parser = parse_output)]
+    output: Vec<Output>,
+    /// Condition that fails the run with a distinct exit code, once the report is printed: failures (3),
+    /// failures-above=N (4), pass-rate-below=X (5), no-tests (6) or flaky (7). Can be repeated
+    #[arg(long)]
+    fail_on: Vec<FailOn>,
+    /// Whether to send the report to Slack, besides printing it. Uses the Web API when a bot token
+    /// is given, and the incoming webhook otherwise
+    #[cfg(feature = "send")]
+    #[arg(long, default_value_t = false)]
+    send: bool,
+    /// The Slack incoming webhook URL the report is sent to
+    #[cfg(feature = "send")]
+    #[arg(long, env = "SLACK_WEBHOOK_URL", hide_env_values = true)]
+    slack_webhook_url: Option<Url>,
+    /// The Slack bot token used to post the report as a thread, with the Web API
+    #[cfg(feature = "send")]
+    #[arg(long, env = "SLACK_BOT_TOKEN", hide_env_values = true)]
+    slack_token: Option<String>,
+    /// The Slack channel the thread is posted to
+    #[cfg(feature = "send")]
+    #[arg(long, env = "SLACK_CHANNEL")]
+    slack_channel: Option<String>,
+    /// Optional ts of an earlier message to update with the report summary, rather than posting it
+    #[cfg(feature = "send")]
+    #[arg(long, requires = "slack_token")]
+    slack_update_ts: Option<String>,
+    /// The URL of a webhook the report is sent to, rendered from the webhook template
+    #[cfg(feature = "webhook")]
+    #[arg(long, requires = "webhook_template")]
+    webhook_url: Option<Url>,
+    /// The path of the minijinja template, rendered against the report, that is sent to the webhook
+    #[cfg(feature = "webhook")]
+    #[arg(long, requires = "webhook_url")]
+    webhook_template: Option<PathBuf>,
+    /// Header of the requests to the webhook, as `Name: value`. Can be repeated
+    #[cfg(feature = "webhook")]
+    #[arg(long, requires = "webhook_url")]
+    webhook_header: Vec<String>,
+    /// The OTLP/HTTP endpoint the trace of the test run is exported to, with all the tests included
+    #[cfg(feature = "otlp")]
+    #[arg(long)]
+    otlp_endpoint: Option<Url>,
 }
 
-impl From<CliArgs> for CreateTestReportRequest {
-    fn from(value: CliArgs) -> Self {
-        let reports_contents: Vec<String> = value
+#[derive(Args)]
+struct MergeArgs {
+    #[command(flatten)]
+    input: InputArgs,
+    /// The name of the merged test suites [default: testvox]
+    #[arg(short, long)]
+    title: Option<String>,
+    /// The path of the merged JUnit file. Printed to the standard output when not given
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct ConvertArgs {
+    #[command(flatten)]
+    input: InputArgs,
+    /// The format the test results are converted to
+    #[arg(long, value_parser = format_names())]
+    to: String,
+    /// The title of the converted report [default: testvox]
+    #[arg(short, long)]
+    title: Option<String>,
+    /// Optional link to view more details related to the report, usually a CI workflow
+    #[arg(short, long, default_value = None)]
+    link: Option<Url>,
+    /// The path of the converted report. Printed to the standard output when not given
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+/// How the report is sent to Slack
+#[cfg(feature = "send")]
+enum Delivery {
+    Webhook(Url),
+    WebApi {
+        token: String,
+        channel: String,
+        update_ts: Option<String>,
+    },
+}
+
+fn missing_argument(message: &str) -> ! {
+    Cli::command()
+        .error(ErrorKind::MissingRequiredArgument, message)
+        .exit()
+}
+
+#[cfg(feature = "send")]
+impl ReportArgs {
+    fn delivery(&self) -> Option<Delivery> {
+        if !self.send {
+            return None;
+        }
+
+        match (&self.slack_token, &self.slack_webhook_url) {
+            (Some(token), _) => Some(Delivery::WebApi {
+                token: token.clone(),
+                channel: self.slack_channel.clone().unwrap_or_else(|| {
+                    missing_argument(
+                        "--slack-token requires --slack-channel, or the SLACK_CHANNEL variable",
+                    )
+                }),
+                update_ts: self.slack_update_ts.clone(),
+            }),
+            (None, Some(url)) => Some(Delivery::Webhook(url.clone())),
+            (None, None) => missing_argument(
+                "--send requires --slack-webhook-url or --slack-token, or the matching variables",
+            ),
+        }
+    }
+}
+
+/// The test report patterns looked for, when given neither on the command line nor in the configuration
+const DEFAULT_REPORTS_PATTERN: [&str; 2] = [
+    "./build/test-results/**/*.xml",
+    "./app/build/test-results/**/*.xml",
+];
+
+impl InputArgs {
+    fn paths(&self, config: &Config) -> Result<Vec<PathBuf>, TestvoxError> {
+        let patterns = self
             .reports_pattern
-            .into_iter()
-            .flat_map(|pattern| {
-                glob(&pattern)
-                    .expect("Unable to use given file pattern")
-                    .filter_map(|test_file| test_file.ok())
-                    .collect::<Vec<PathBuf>>()
-            })
-            .filter_map(|path| fs::read_to_string(path).ok())
-            .collect();
+            .clone()
+            .or_else(|| config.reports_pattern.clone())
+            .unwrap_or_else(|| DEFAULT_REPORTS_PATTERN.map(String::from).to_vec());
+
+        find_test_results_files(&patterns)
+    }
+
+    /// Reads the test results of all the files, warning about the ones that can't be read or parsed,
+    /// unless in strict mode. Finding no file is an error, unless allowed.
+    fn test_results(&self, config: &Config, allow_no_files: bool) -> Vec<TestResult> {
+        let paths = match self.paths(config) {
+            Ok(paths) => paths,
+            Err(TestvoxError::NoFilesMatched { .. }) if allow_no_files => return vec![],
+            Err(e) => fail(e),
+        };
 
-        if reports_contents.is_empty() {
-            panic!("Cannot find test results file");
+        let mut test_results = vec![];
+        for path in paths {
+            match read_test_results_file(&path) {
+                Ok(file_test_results) => test_results.extend(file_test_results),
+                Err(e) if flag(self.strict, self.no_strict).unwrap_or(config.strict) => fail(e),
+                Err(e) => eprintln!("warning: {}", e),
+            }
         }
 
-        Self {
-            title: value.title,
-            reports_contents,
-            include_passed: value.include_passed,
-            include_skipped: value.include_skipped,
-            link: value.link,
+        test_results
+    }
+}
+
+impl ReportArgs {
+    fn request(&self, config: &Config, fail_on: &[FailOn]) -> CreateTestReportRequest {
+        CreateTestReportRequest {
+            title: self
+                .title
+                .clone()
+                .or_else(|| config.title.clone())
+                .unwrap_or_else(|| {
+                    missing_argument("--title is required, unless set in the configuration file")
+                }),
+            // missing test results are reported by the exit code when asked, once the report is printed
+            test_results: self
+                .input
+                .test_results(config, fail_on.contains(&FailOn::NoTests)),
+            include_passed: flag(self.include_passed, self.no_include_passed)
+                .unwrap_or(config.include_passed),
+            include_skipped: flag(self.include_skipped, self.no_include_skipped)
+                .unwrap_or(config.include_skipped),
+            link: self.link.clone().or_else(|| config.link.clone()),
+            ..Default::default()
         }
     }
 }
 
+/// Resolves a flag given along with its `--no-` counterpart, the last one given winning
+fn flag(enabled: bool, disabled: bool) -> Option<bool> {
+    match (enabled, disabled) {
+        (true, _) => Some(true),
+        (_, true) => Some(false),
+        _ => None,
+    }
+}
+
 fn main() {
-    let cli_args = CliArgs::parse();
+    let cli = Cli::parse_from(args());
+    let config = or_exit(
+        load_config(cli.config.as_deref()),
+        "Unable to load the configuration",
+    );
+
+    match cli.command {
+        Command::Report(args) => report(*args, &config),
+        Command::Summary(args) => summary(args, &config),
+        Command::Merge(args) => convert(
+            ConvertArgs {
+                input: args.input,
+                to: "junit".to_string(),
+                title: args.title,
+                link: None,
+                output: args.output,
+            },
+            &config,
+        ),
+        Command::Convert(args) => convert(args, &config),
+        Command::Validate(args) => validate(args, &config),
+    }
+}
 
-    let report: SlackReport = create_test_report(cli_args.into());
+/// Yields the command line arguments, with the `report` command inserted when no command is given
+/// after the configuration file, so that the flat invocation of earlier versions keeps working
+fn args() -> Vec<OsString> {
+    let mut args: Vec<OsString> = std::env::args_os().collect();
+
+    let mut position = 1;
+    while let Some(arg) = args.get(position).and_then(|arg| arg.to_str()) {
+        match arg {
+            "-c" | "--config" => position += 2,
+            _ if arg.starts_with("--config=") => position += 1,
+            _ => break,
+        }
+    }
+
+    let insert = match args.get(position).and_then(|arg| arg.to_str()) {
+        Some(arg) => arg.starts_with('-') && arg != "-h" && arg != "--help",
+        None => position > 1,
+    };
+    if insert {
+        args.insert(1, OsString::from("report"));
+    }
 
-    println!("{}", report.to_string_pretty())
+    args
+}
+
+/// Loads the given configuration file, or the one in the working directory when found
+fn load_config(path: Option<&Path>) -> anyhow::Result<Config> {
+    match path {
+        Some(path) => Config::load(path),
+        None if Path::new(CONFIG_FILE_NAME).is_file() => Config::load(Path::new(CONFIG_FILE_NAME)),
+        None => Ok(Config::default()),
+    }
+}
+
+fn fail(error: TestvoxError) -> ! {
+    eprintln!("error: {}", error);
+    std::process::exit(1);
+}
+
+fn or_exit<T>(result: anyhow::Result<T>, message: &str) -> T {
+    result.unwrap_or_else(|e| {
+        eprintln!("{}: {:#}", message, e);
+        std::process::exit(1);
+    })
+}
+
+fn render(registry: &ReporterRegistry, format: &str, report: &Report) -> Vec<u8> {
+    registry.render(format, report).unwrap_or_else(|e| {
+        eprintln!("Unable to render the {} report: {}", format, e);
+        std::process::exit(1);
+    })
+}
+
+fn write(path: &Path, contents: Vec<u8>) {
+    if let Err(e) = fs::write(path, contents) {
+        eprintln!("Unable to write {}: {}", path.display(), e);
+        std::process::exit(1);
+    }
+}
+
+fn report(args: ReportArgs, config: &Config) {
+    #[cfg(feature = "send")]
+    let delivery = args.delivery();
+    #[cfg(feature = "webhook")]
+    let webhook = (
+        args.webhook_url.clone(),
+        args.webhook_template.clone(),
+        args.webhook_header.clone(),
+    );
+
+    let format = args
+        .format
+        .clone()
+        .or_else(|| config.format.clone())
+        .unwrap_or_else(|| "slack".to_string());
+    let outputs = match args.output.is_empty() {
+        true => config
+            .outputs
+            .iter()
+            .map(|(format, path)| Output {
+                format: format.clone(),
+                path: path.clone(),
+            })
+            .collect(),
+        false => args.output.clone(),
+    };
+
+    let fail_on = match args.fail_on.is_empty() {
+        true => config.fail_on.clone(),
+        false => args.fail_on.clone(),
+    };
+
+    let registry = config.registry();
+    let builder = create_report_builder(args.request(config, &fail_on)).unwrap_or_else(|e| fail(e));
+    let builder = or_exit(config.apply(builder), "Unable to apply the configuration");
+    // traces are meant to inspect the whole run, so they include the tests of all statuses
+    #[cfg(feature = "otlp")]
+    let trace = args.otlp_endpoint.as_ref().map(|_| {
+        builder
+            .clone()
+            .include_passed()
+            .include_skipped()
+            .build::<testvox::reporters::otlp::OtlpTraceReport>()
+    });
+    let report = builder.build::<Report>();
+
+    println!(
+        "{}",
+        String::from_utf8_lossy(&render(&registry, &format, &report))
+    );
+
+    for output in outputs {
+        write(&output.path, render(&registry, &output.format, &report));
+    }
+
+    #[cfg(feature = "webhook")]
+    if let (Some(url), Some(template), headers) = webhook {
+        if let Err(e) = send_to_webhook(url, &template, &headers, &report) {
+            eprintln!("Unable to send the report to the webhook: {:#}", e);
+            std::process::exit(1);
+        }
+    }
+
+    #[cfg(feature = "otlp")]
+    if let (Some(endpoint), Some(trace)) = (&args.otlp_endpoint, trace) {
+        if let Err(e) = testvox::senders::otlp::export(&trace, endpoint, &[]) {
+            eprintln!("Unable to export the trace: {:#}", e);
+            std::process::exit(1);
+        }
+    }
+
+    #[cfg(feature = "send")]
+    if let Some(delivery) = delivery {
+        if let Err(e) = deliver(delivery, &report) {
+            eprintln!("Unable to send the report: {:#}", e);
+            std::process::exit(1);
+        }
+    }
+
+    if let Some(condition) = quality_gate::check(&fail_on, &report) {
+        eprintln!("Quality gate failed: {}", condition);
+        std::process::exit(condition.exit_code());
+    }
+}
+
+fn summary(args: InputArgs, config: &Config) {
+    let builder = create_report_builder(CreateTestReportRequest {
+        test_results: args.test_results(config, false),
+        ..Default::default()
+    })
+    .unwrap_or_else(|e| fail(e));
+    let summary = or_exit(
+        config.apply_filters(builder),
+        "Unable to apply the configuration",
+    )
+    .build::<Report>()
+    .summary;
+
+    println!(
+        "total={} passed={} failed={} skipped={} flaky={} duration={:.3}",
+        summary.total(),
+        summary.passed,
+        summary.failed,
+        summary.skipped,
+        summary.flaky,
+        summary.execution_time
+    );
+}
+
+fn convert(args: ConvertArgs, config: &Config) {
+    let builder = create_report_builder(CreateTestReportRequest {
+        title: args
+            .title
+            .or_else(|| config.title.clone())
+            .unwrap_or_else(|| "testvox".to_string()),
+        test_results: args.input.test_results(config, false),
+        include_passed: true,
+        include_skipped: true,
+        link: args.link.or_else(|| config.link.clone()),
+        ..Default::default()
+    })
+    .unwrap_or_else(|e| fail(e));
+    let report = or_exit(
+        config.apply_filters(builder),
+        "Unable to apply the configuration",
+    )
+    .build::<Report>();
+
+    let converted = render(&config.registry(), &args.to, &report);
+
+    match args.output {
+        Some(path) => write(&path, converted),
+        None => println!("{}", String::from_utf8_lossy(&converted)),
+    }
+}
+
+fn validate(args: InputArgs, config: &Config) {
+    let paths = args.paths(config).unwrap_or_else(|e| fail(e));
+
+    let mut valid = true;
+    for path in paths {
+        match read_test_results_file(&path) {
+            Ok(test_results) => {
+                println!("ok    {} ({} tests)", path.display(), test_results.len())
+            }
+            Err(e) => {
+                valid = false;
+                println!("error {}", e)
+            }
+        }
+    }
+
+    if !valid {
+        std::process::exit(1);
+    }
+}
+
+#[cfg(feature = "webhook")]
+fn send_to_webhook(
+    url: Url,
+    template: &Path,
+    headers: &[String],
+    report: &Report,
+) -> anyhow::Result<()> {
+    use anyhow::Context;
+    use testvox::senders::webhook::{parse_header, Webhook};
+
+    let template = fs::read_to_string(template)
+        .with_context(|| format!("Unable to read template {}", template.display()))?;
+
+    let mut webhook = Webhook::new(url, template);
+    for header in headers {
+        let (name, value) = parse_header(header)?;
+        webhook = webhook.with_header(name, value);
+    }
+
+    webhook.send(report)
+}
+
+#[cfg(feature = "send")]
+fn deliver(delivery: Delivery, report: &Report) -> anyhow::Result<()> {
+    use testvox::{
+        models::test_report::ReportBuilder,
+        reporters::slack::{SlackReport, SlackThreadReport},
+        senders::{
+            retry::RetryPolicy,
+            slack::{send, SlackWebApi},
+        },
+    };
+
+    match delivery {
+        Delivery::Webhook(url) => send(
+            &SlackReport::from(ReportBuilder::from(report)),
+            &url,
+            &RetryPolicy::default(),
+        ),
+        Delivery::WebApi {
+            token,
+            channel,
+            update_ts,
+        } => {
+            let thread = SlackThreadReport::from(ReportBuilder::from(report));
+            let ts =
+                SlackWebApi::new(token).post_thread(&thread, &channel, update_ts.as_deref())?;
+            eprintln!("Report posted to Slack, with ts {}", ts);
+            Ok(())
+        }
+    }
 }
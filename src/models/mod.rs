@@ -4,3 +4,5 @@ pub mod test_report;
 pub mod test_result;
 /// test status related types
 pub mod test_status;
+/// test summary related types
+pub mod test_summary;
@@ -1,9 +1,11 @@
-use super::{test_result::TestResult, test_status::TestStatus};
-use std::collections::HashSet;
+use super::{test_result::TestResult, test_status::TestStatus, test_summary::TestSummary};
+use glob::Pattern;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use url::Url;
 
 /// Responsible for building the Report domain object.
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct ReportBuilder {
     /// the title of the report
     pub(crate) title: String,
@@ -13,6 +15,18 @@ pub struct ReportBuilder {
     pub(crate) reportable_statuses: HashSet<TestStatus>,
     /// optional link to the test report failing on CI/other systems
     pub(crate) link: Option<Url>,
+    /// patterns of the names of the tests to keep, all tests are kept when empty
+    pub(crate) include_patterns: Vec<Pattern>,
+    /// patterns of the names of the tests to leave out, even when matching the included ones
+    pub(crate) exclude_patterns: Vec<Pattern>,
+    /// optional upper bound of the number of reported test results
+    pub(crate) max_test_results: Option<usize>,
+    /// optional upper bound of the length of failure messages, in characters
+    pub(crate) max_failure_message_length: Option<usize>,
+    /// counters over all the test results, computed before filtering by status
+    pub(crate) summary: TestSummary,
+    /// counters over all the test results by suite name, computed before filtering by status
+    pub(crate) suite_summaries: Vec<(String, TestSummary)>,
 }
 
 /// Implementation of the report builder
@@ -48,19 +62,184 @@ impl ReportBuilder {
         self
     }
 
+    /// Keeps the tests whose name, or suite name, matches the given pattern only. Can be repeated
+    pub fn include_tests_matching(mut self, pattern: Pattern) -> ReportBuilder {
+        self.include_patterns.push(pattern);
+        self
+    }
+
+    /// Leaves out the tests whose name, or suite name, matches the given pattern. Can be repeated
+    pub fn exclude_tests_matching(mut self, pattern: Pattern) -> ReportBuilder {
+        self.exclude_patterns.push(pattern);
+        self
+    }
+
+    /// Reports the given number of test results at most, failures first. Counters still cover all tests
+    pub fn with_max_test_results(mut self, max_test_results: usize) -> ReportBuilder {
+        self.max_test_results = Some(max_test_results);
+        self
+    }
+
+    /// Truncates failure messages longer than the given number of characters
+    pub fn with_max_failure_message_length(mut self, max_length: usize) -> ReportBuilder {
+        self.max_failure_message_length = Some(max_length);
+        self
+    }
+
     /// Builds a report of the generic type `T`
     pub fn build<T>(mut self) -> T
     where
         T: From<ReportBuilder> + PrettyPrint,
     {
+        let (include_patterns, exclude_patterns) = (&self.include_patterns, &self.exclude_patterns);
+        self.test_results.retain(|t| {
+            (include_patterns.is_empty() || include_patterns.iter().any(|p| matches(p, t)))
+                && !exclude_patterns.iter().any(|p| matches(p, t))
+        });
+        self.test_results = collapse_retried_tests(self.test_results);
+
+        self.summary = TestSummary::from(self.test_results.as_slice());
+        self.suite_summaries = TestSummary::by_suite(self.test_results.as_slice());
         self.test_results
             .retain(|t| self.reportable_statuses.contains(&t.status));
         self.test_results.sort_by(|a, b| a.status.cmp(&b.status));
 
+        if let Some(max_test_results) = self.max_test_results {
+            self.test_results.truncate(max_test_results);
+        }
+
+        if let Some(max_length) = self.max_failure_message_length {
+            for test_result in self.test_results.iter_mut() {
+                if let Some(message) = test_result.failure_message.as_mut() {
+                    truncate(message, max_length);
+                }
+            }
+        }
+
         Into::into(self)
     }
 }
 
+/// Collapses the tests that were retried, such as the ones reported in several files or several
+/// times in the same file, into a single result with their final status, where passing wins.
+/// Tests that both failed and passed are marked as flaky.
+fn collapse_retried_tests(test_results: Vec<TestResult>) -> Vec<TestResult> {
+    let identity = |t: &TestResult| (t.suite_name.clone(), t.classname.clone(), t.name.clone());
+
+    let mut statuses: HashMap<(Option<String>, Option<String>, String), HashSet<TestStatus>> =
+        HashMap::new();
+    let mut positions: HashMap<(Option<String>, Option<String>, String), usize> = HashMap::new();
+    let mut collapsed: Vec<TestResult> = vec![];
+    for t in test_results {
+        let id = identity(&t);
+        statuses
+            .entry(id.clone())
+            .or_default()
+            .insert(t.status.clone());
+
+        match positions.get(&id) {
+            Some(&i) => {
+                let flaky = collapsed[i].flaky || t.flaky;
+                if collapsed[i].status != TestStatus::Passed || t.status == TestStatus::Passed {
+                    collapsed[i] = t;
+                }
+                collapsed[i].flaky = flaky;
+            }
+            None => {
+                positions.insert(id, collapsed.len());
+                collapsed.push(t);
+            }
+        }
+    }
+
+    for t in collapsed.iter_mut() {
+        let statuses = &statuses[&identity(t)];
+        if statuses.contains(&TestStatus::Failed) && statuses.contains(&TestStatus::Passed) {
+            t.flaky = true;
+        }
+    }
+
+    collapsed
+}
+
+fn matches(pattern: &Pattern, test_result: &TestResult) -> bool {
+    pattern.matches(&test_result.name)
+        || test_result
+            .suite_name
+            .as_ref()
+            .is_some_and(|suite_name| pattern.matches(suite_name))
+}
+
+/// Truncates the text to the given number of characters, ellipsis included
+pub(crate) fn truncate(text: &mut String, max_length: usize) {
+    if text.chars().count() > max_length {
+        *text = text.chars().take(max_length.saturating_sub(1)).collect();
+        text.push('…');
+    }
+}
+
+/// Report domain object, holding the test results selected for reporting along with a summary of
+/// all the parsed ones
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Report {
+    /// the title of the report
+    pub title: String,
+    /// optional link to the test report failing on CI/other systems
+    pub link: Option<Url>,
+    /// counters over all the parsed test results
+    pub summary: TestSummary,
+    /// the reported test results, ordered by status
+    pub test_results: Vec<TestResult>,
+    /// counters over all the parsed test results by suite name. Not serialized, as they are only
+    /// known when the report is built out of the parsed test results.
+    #[serde(skip)]
+    pub suite_summaries: Vec<(String, TestSummary)>,
+}
+
+/// Turns test results into a report domain object
+impl From<ReportBuilder> for Report {
+    fn from(value: ReportBuilder) -> Self {
+        Report {
+            title: value.title,
+            link: value.link,
+            summary: value.summary,
+            test_results: value.test_results,
+            suite_summaries: value.suite_summaries,
+        }
+    }
+}
+
+/// Turns a report domain object back into a builder, keeping its counters as they are. Report types
+/// should be created out of it with `T::from` rather than `build`, which would count the reported
+/// test results only.
+impl From<&Report> for ReportBuilder {
+    fn from(value: &Report) -> Self {
+        let mut reportable_statuses: HashSet<TestStatus> = value
+            .test_results
+            .iter()
+            .map(|t| t.status.clone())
+            .collect();
+        reportable_statuses.insert(TestStatus::Failed);
+
+        ReportBuilder {
+            title: value.title.clone(),
+            test_results: value.test_results.clone(),
+            reportable_statuses,
+            link: value.link.clone(),
+            summary: value.summary.clone(),
+            suite_summaries: value.suite_summaries.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Pretty print implementation for the report domain object
+impl PrettyPrint for Report {
+    fn to_string_pretty(&self) -> String {
+        serde_json::to_string_pretty(&self).expect("unable to serialize report to JSON")
+    }
+}
+
 /// Trait that define the function that should be implemented for pretty printing a report
 pub trait PrettyPrint {
     // Utility that produce a report in pretty format
@@ -71,7 +250,8 @@ pub trait PrettyPrint {
 mod tests {
     use crate::models::{test_result::TestResult, test_status::TestStatus};
 
-    use super::{PrettyPrint, ReportBuilder};
+    use super::{PrettyPrint, Report, ReportBuilder};
+    use glob::Pattern;
     use serde::Serialize;
     use url::Url;
 
@@ -115,6 +295,108 @@ mod tests {
             "a-report|[(\"a-test-failed\", \"Failed\"), (\"a-test-skipped\", \"Skipped\"), (\"a-test-passed\", \"Passed\")]|http://localhost/test-run");
     }
 
+    #[test]
+    fn should_filter_tests_by_name_before_counting_them() {
+        let test_results: Vec<TestResult> = vec![
+            TestResult::builder()
+                .with_name("a-test-failed".to_string())
+                .with_suite_name("Suite.A".to_string())
+                .with_status(TestStatus::Failed)
+                .build(),
+            TestResult::builder()
+                .with_name("a-flaky-test-failed".to_string())
+                .with_suite_name("Suite.A".to_string())
+                .with_status(TestStatus::Failed)
+                .build(),
+            TestResult::builder()
+                .with_name("another-test-failed".to_string())
+                .with_suite_name("Suite.B".to_string())
+                .with_status(TestStatus::Failed)
+                .build(),
+        ];
+
+        let report = ReportBuilder::new()
+            .with_test_results(test_results)
+            .include_tests_matching(Pattern::new("Suite.A").unwrap())
+            .exclude_tests_matching(Pattern::new("*flaky*").unwrap())
+            .build::<Report>();
+
+        assert_eq!(report.summary.failed, 1);
+        assert_eq!(report.test_results.len(), 1);
+        assert_eq!(report.test_results[0].name, "a-test-failed");
+    }
+
+    #[test]
+    fn should_collapse_tests_that_failed_and_passed_when_retried_as_flaky() {
+        let test_results: Vec<TestResult> = vec![
+            TestResult::builder()
+                .with_name("a-retried-test".to_string())
+                .with_suite_name("Suite.A".to_string())
+                .with_status(TestStatus::Failed)
+                .build(),
+            TestResult::builder()
+                .with_name("a-retried-test".to_string())
+                .with_suite_name("Suite.A".to_string())
+                .with_status(TestStatus::Passed)
+                .build(),
+            TestResult::builder()
+                .with_name("a-retried-test".to_string())
+                .with_suite_name("Suite.B".to_string())
+                .with_status(TestStatus::Failed)
+                .build(),
+        ];
+
+        let report = ReportBuilder::new()
+            .with_test_results(test_results)
+            .include_passed()
+            .build::<Report>();
+
+        assert_eq!(report.summary.total(), 2);
+        assert_eq!(report.summary.passed, 1);
+        assert_eq!(report.summary.failed, 1);
+        assert_eq!(report.summary.flaky, 1);
+        assert_eq!(
+            report
+                .test_results
+                .iter()
+                .map(|t| (t.suite_name.as_deref(), t.status.clone(), t.flaky))
+                .collect::<Vec<(Option<&str>, TestStatus, bool)>>(),
+            vec![
+                (Some("Suite.B"), TestStatus::Failed, false),
+                (Some("Suite.A"), TestStatus::Passed, true)
+            ]
+        );
+    }
+
+    #[test]
+    fn should_truncate_reported_test_results() {
+        let test_results: Vec<TestResult> = vec![
+            TestResult::builder()
+                .with_name("a-test-passed".to_string())
+                .with_status(TestStatus::Passed)
+                .build(),
+            TestResult::builder()
+                .with_name("a-test-failed".to_string())
+                .with_status(TestStatus::Failed)
+                .with_failure_message("expected 1, got 2".to_string())
+                .build(),
+        ];
+
+        let report = ReportBuilder::new()
+            .with_test_results(test_results)
+            .include_passed()
+            .with_max_test_results(1)
+            .with_max_failure_message_length(9)
+            .build::<Report>();
+
+        assert_eq!(report.summary.passed, 1);
+        assert_eq!(report.test_results.len(), 1);
+        assert_eq!(
+            report.test_results[0].failure_message.as_deref(),
+            Some("expected…")
+        );
+    }
+
     #[derive(Serialize)]
     struct CustomReport {
         title: String,
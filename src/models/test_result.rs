@@ -1,18 +1,31 @@
+use serde::{Deserialize, Serialize};
+
 use super::test_status::TestStatus;
 
 /// Struct that defines the domain test result object
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct TestResult {
     /// Name of the test
     pub name: String,
     /// Optional name of the suite in which the test is contained
     pub suite_name: Option<String>,
+    /// Optional name of the class, or module, that defines the test
+    pub classname: Option<String>,
+    /// Optional path of the source file that defines the test
+    pub file: Option<String>,
+    /// Optional line, in the source file, at which the test is defined or failed
+    pub line: Option<u32>,
     /// Optional execution time associated to the test
     pub execution_time: Option<f32>,
     /// The status of the test
     pub status: TestStatus,
     /// Optional failure message related to a failed test
     pub failure_message: Option<String>,
+    /// Optional stack trace, or any other failure details, related to a failed test
+    pub stack_trace: Option<String>,
+    /// Whether the test both failed and passed, when retried
+    #[serde(default)]
+    pub flaky: bool,
 }
 
 impl TestResult {
@@ -29,12 +42,22 @@ pub struct TestResultBuilder {
     name: String,
     /// Optional name of the suite in which the test is included
     suite_name: Option<String>,
+    /// Optional name of the class that defines the test
+    classname: Option<String>,
+    /// Optional source file path
+    file: Option<String>,
+    /// Optional source line
+    line: Option<u32>,
     /// Optional execution time
     execution_time: Option<f32>,
     /// Status of the test
     status: TestStatus,
     /// Failure message of a failed test
     failure_message: Option<String>,
+    /// Stack trace of a failed test
+    stack_trace: Option<String>,
+    /// Whether the test is flaky
+    flaky: bool,
 }
 
 impl TestResultBuilder {
@@ -48,6 +71,21 @@ impl TestResultBuilder {
         self
     }
 
+    pub fn with_classname(mut self, classname: String) -> TestResultBuilder {
+        self.classname = Some(classname);
+        self
+    }
+
+    pub fn with_file(mut self, file: String) -> TestResultBuilder {
+        self.file = Some(file);
+        self
+    }
+
+    pub fn with_line(mut self, line: u32) -> TestResultBuilder {
+        self.line = Some(line);
+        self
+    }
+
     pub fn with_execution_time(mut self, execution_time: f32) -> TestResultBuilder {
         self.execution_time = Some(execution_time);
         self
@@ -63,37 +101,88 @@ impl TestResultBuilder {
         self
     }
 
+    pub fn with_stack_trace(mut self, stack_trace: String) -> TestResultBuilder {
+        self.stack_trace = Some(stack_trace);
+        self
+    }
+
+    pub fn with_flaky(mut self, flaky: bool) -> TestResultBuilder {
+        self.flaky = flaky;
+        self
+    }
+
     pub fn build(self) -> TestResult {
         TestResult {
             name: self.name,
             suite_name: self.suite_name,
+            classname: self.classname,
+            file: self.file,
+            line: self.line,
             execution_time: self.execution_time,
             status: self.status,
             failure_message: self.failure_message,
+            stack_trace: self.stack_trace,
+            flaky: self.flaky,
+        }
+    }
+}
+
+/// Groups test results by suite name, in order of appearance. Tests without a suite name are
+/// grouped together, under a placeholder name.
+pub fn group_by_suite(test_results: Vec<TestResult>) -> Vec<(String, Vec<TestResult>)> {
+    let mut suites: Vec<(String, Vec<TestResult>)> = vec![];
+    for test_result in test_results {
+        let suite_name = test_result
+            .suite_name
+            .clone()
+            .unwrap_or("⚠️ missing suite name".to_string());
+
+        match suites.iter_mut().find(|(name, _)| *name == suite_name) {
+            Some((_, tests)) => tests.push(test_result),
+            None => suites.push((suite_name, vec![test_result])),
         }
     }
+    suites
 }
 
 impl TestResult {
     /// Utility that pretty prints the test message, using markdown syntax
     pub(crate) fn to_markdown_string(&self) -> String {
+        self.to_string_with_bold_marker("*")
+    }
+
+    /// Utility that pretty prints the test message, using CommonMark syntax rather than Slack's
+    /// flavour of markdown
+    pub(crate) fn to_commonmark_string(&self) -> String {
+        self.to_string_with_bold_marker("**")
+    }
+
+    /// Utility that yields the name of the test prefixed by the name of its suite, if any
+    pub(crate) fn qualified_name(&self) -> String {
+        match &self.suite_name {
+            Some(suite_name) => format!("{} › {}", suite_name, self.name),
+            None => self.name.clone(),
+        }
+    }
+
+    fn to_string_with_bold_marker(&self, bold: &str) -> String {
         match self.status {
             TestStatus::Passed => {
                 format!(
-                    "✅ _{}_ *passed* (`{}s`)",
+                    "✅ _{}_ {bold}passed{bold} (`{}s`)",
                     self.name,
                     self.execution_time.unwrap_or(0.0)
                 )
             }
             TestStatus::Failed => format!(
-                "❌ _{}_ *failed* (`{}s`): ```{}```",
+                "❌ _{}_ {bold}failed{bold} (`{}s`): ```{}```",
                 self.name,
                 self.execution_time.unwrap_or(0.0),
                 self.failure_message
                     .clone()
                     .unwrap_or("⚠️ missing failure message".to_string())
             ),
-            TestStatus::Skipped => format!("⏭️ _{}_ was *skipped*", self.name),
+            TestStatus::Skipped => format!("⏭️ _{}_ was {bold}skipped{bold}", self.name),
         }
     }
 }
@@ -102,28 +191,46 @@ impl TestResult {
 mod tests {
     use test_case::test_case;
 
-    use crate::models::{test_result::TestResult, test_status::TestStatus};
+    use crate::models::{
+        test_result::{group_by_suite, TestResult},
+        test_status::TestStatus,
+    };
 
     #[test_case(TestResult {
         name: "SomeTest".to_string(),
         suite_name: Some("A test suite".to_string()),
+        classname: None,
+        file: None,
+        line: None,
         execution_time: Some(2.4),
         status: TestStatus::Failed,
         failure_message: Some("A timeout occurred".to_string()),
+        stack_trace: None,
+        flaky: false,
     }, "❌ _SomeTest_ *failed* (`2.4s`): ```A timeout occurred```" ; "test failed")]
     #[test_case(TestResult {
         name: "AnotherTest".to_string(),
         suite_name: Some("A test suite".to_string()),
+        classname: None,
+        file: None,
+        line: None,
         execution_time: None,
         status: TestStatus::Skipped,
         failure_message: None,
+        stack_trace: None,
+        flaky: false,
     }, "⏭️ _AnotherTest_ was *skipped*"; "test skipped")]
     #[test_case(TestResult {
         name: "PassedTest".to_string(),
         suite_name: Some("A test suite".to_string()),
+        classname: None,
+        file: None,
+        line: None,
         execution_time: Some(2.4),
         status: TestStatus::Passed,
         failure_message: None,
+        stack_trace: None,
+        flaky: false,
     }, "✅ _PassedTest_ *passed* (`2.4s`)" ; "test passed")]
     fn trait_should_convert_to_markdown_test_message(
         test_result: TestResult,
@@ -134,6 +241,51 @@ mod tests {
         assert_eq!(actual_markdown_message, expected_markdown_message);
     }
 
+    #[test_case(TestResult {
+        name: "SomeTest".to_string(),
+        suite_name: Some("A test suite".to_string()),
+        classname: None,
+        file: None,
+        line: None,
+        execution_time: Some(2.4),
+        status: TestStatus::Failed,
+        failure_message: Some("A timeout occurred".to_string()),
+        stack_trace: None,
+        flaky: false,
+    }, "❌ _SomeTest_ **failed** (`2.4s`): ```A timeout occurred```" ; "test failed")]
+    #[test_case(TestResult {
+        name: "AnotherTest".to_string(),
+        suite_name: Some("A test suite".to_string()),
+        classname: None,
+        file: None,
+        line: None,
+        execution_time: None,
+        status: TestStatus::Skipped,
+        failure_message: None,
+        stack_trace: None,
+        flaky: false,
+    }, "⏭️ _AnotherTest_ was **skipped**"; "test skipped")]
+    #[test_case(TestResult {
+        name: "PassedTest".to_string(),
+        suite_name: Some("A test suite".to_string()),
+        classname: None,
+        file: None,
+        line: None,
+        execution_time: Some(2.4),
+        status: TestStatus::Passed,
+        failure_message: None,
+        stack_trace: None,
+        flaky: false,
+    }, "✅ _PassedTest_ **passed** (`2.4s`)" ; "test passed")]
+    fn trait_should_convert_to_commonmark_test_message(
+        test_result: TestResult,
+        expected_markdown_message: &str,
+    ) {
+        let actual_markdown_message = test_result.to_commonmark_string();
+
+        assert_eq!(actual_markdown_message, expected_markdown_message);
+    }
+
     #[test]
     fn builder_should_build_a_test_result() {
         let t = TestResult::builder()
@@ -142,6 +294,7 @@ mod tests {
             .with_status(TestStatus::Failed)
             .with_failure_message("something bad happened".to_string())
             .with_suite_name("a suite name".to_string())
+            .with_classname("a.class.Name".to_string())
             .build();
 
         assert_eq!(t.name, "a test name");
@@ -152,5 +305,56 @@ mod tests {
             Some("something bad happened".to_string())
         );
         assert_eq!(t.suite_name, Some("a suite name".to_string()));
+        assert_eq!(t.classname, Some("a.class.Name".to_string()));
+    }
+
+    #[test]
+    fn should_qualify_test_names_with_their_suite() {
+        let test_result = TestResult::builder()
+            .with_name("a test".to_string())
+            .build();
+        assert_eq!(test_result.qualified_name(), "a test");
+
+        let test_result = TestResult::builder()
+            .with_name("a test".to_string())
+            .with_suite_name("Suite.A".to_string())
+            .build();
+        assert_eq!(test_result.qualified_name(), "Suite.A › a test");
+    }
+
+    #[test]
+    fn should_group_test_results_by_suite_in_order_of_appearance() {
+        let test_result = |name: &str, suite_name: Option<&str>| {
+            let mut builder = TestResult::builder().with_name(name.to_string());
+            if let Some(suite_name) = suite_name {
+                builder = builder.with_suite_name(suite_name.to_string());
+            }
+            builder.build()
+        };
+
+        let suites = group_by_suite(vec![
+            test_result("test 1", Some("Suite.B")),
+            test_result("test 2", Some("Suite.A")),
+            test_result("test 3", None),
+            test_result("test 4", Some("Suite.B")),
+        ]);
+
+        let suites: Vec<(&str, Vec<&str>)> = suites
+            .iter()
+            .map(|(suite_name, tests)| {
+                (
+                    suite_name.as_str(),
+                    tests.iter().map(|t| t.name.as_str()).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            suites,
+            vec![
+                ("Suite.B", vec!["test 1", "test 4"]),
+                ("Suite.A", vec!["test 2"]),
+                ("⚠️ missing suite name", vec!["test 3"]),
+            ]
+        );
     }
 }
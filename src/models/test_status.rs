@@ -1,8 +1,10 @@
 use core::fmt;
+use serde::{Deserialize, Serialize};
 use std::{cmp::Ordering, fmt::Formatter};
 
 /// Enum that defines the universe of test statuses
-#[derive(PartialEq, Eq, Hash, Default, Clone, Debug)]
+#[derive(PartialEq, Eq, Hash, Default, Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum TestStatus {
     #[default]
     Failed,
@@ -44,7 +46,7 @@ mod tests {
 
     #[test]
     fn list_should_be_ordered_based_on_status() {
-        let mut test_results = vec![
+        let mut test_results = [
             TestResult::builder()
                 .with_status(TestStatus::Skipped)
                 .build(),
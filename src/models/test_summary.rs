@@ -0,0 +1,150 @@
+use serde::{Deserialize, Serialize};
+
+use super::{
+    test_result::{group_by_suite, TestResult},
+    test_status::TestStatus,
+};
+
+/// Struct that defines aggregated counters over a list of test results
+#[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TestSummary {
+    /// Number of passed tests
+    pub passed: usize,
+    /// Number of failed tests
+    pub failed: usize,
+    /// Number of skipped tests
+    pub skipped: usize,
+    /// Sum of the execution times of all tests, in seconds
+    pub execution_time: f32,
+    /// Number of flaky tests, regardless of their status
+    #[serde(default)]
+    pub flaky: usize,
+}
+
+impl TestSummary {
+    /// Total number of tests, regardless of their status
+    pub fn total(&self) -> usize {
+        self.passed + self.failed + self.skipped
+    }
+
+    /// Summarizes test results by suite name, in order of appearance. Tests without a suite name
+    /// are left out.
+    pub fn by_suite(test_results: &[TestResult]) -> Vec<(String, TestSummary)> {
+        let with_suite_name = test_results
+            .iter()
+            .filter(|t| t.suite_name.is_some())
+            .cloned()
+            .collect();
+
+        group_by_suite(with_suite_name)
+            .into_iter()
+            .map(|(suite_name, tests)| (suite_name, TestSummary::from(tests.as_slice())))
+            .collect()
+    }
+
+    fn add(&mut self, test_result: &TestResult) {
+        match test_result.status {
+            TestStatus::Passed => self.passed += 1,
+            TestStatus::Failed => self.failed += 1,
+            TestStatus::Skipped => self.skipped += 1,
+        }
+        self.execution_time += test_result.execution_time.unwrap_or(0.0);
+        if test_result.flaky {
+            self.flaky += 1;
+        }
+    }
+
+    /// Ratio of the passed tests over the executed ones, skipped tests aside. Yields `None` when
+    /// no test was executed
+    pub fn pass_rate(&self) -> Option<f32> {
+        match self.passed + self.failed {
+            0 => None,
+            executed => Some(self.passed as f32 / executed as f32),
+        }
+    }
+}
+
+/// Aggregates a list of test results into a summary
+impl From<&[TestResult]> for TestSummary {
+    fn from(test_results: &[TestResult]) -> Self {
+        test_results
+            .iter()
+            .fold(TestSummary::default(), |mut summary, t| {
+                summary.add(t);
+                summary
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{test_result::TestResult, test_status::TestStatus};
+
+    use super::TestSummary;
+
+    #[test]
+    fn should_summarize_test_results() {
+        let test_results = vec![
+            TestResult::builder()
+                .with_status(TestStatus::Passed)
+                .with_execution_time(1.5)
+                .build(),
+            TestResult::builder()
+                .with_status(TestStatus::Failed)
+                .with_execution_time(0.5)
+                .build(),
+            TestResult::builder()
+                .with_status(TestStatus::Skipped)
+                .build(),
+            TestResult::builder()
+                .with_status(TestStatus::Passed)
+                .with_execution_time(1.0)
+                .with_flaky(true)
+                .build(),
+        ];
+
+        let summary = TestSummary::from(test_results.as_slice());
+
+        assert_eq!(summary.passed, 2);
+        assert_eq!(summary.failed, 1);
+        assert_eq!(summary.skipped, 1);
+        assert_eq!(summary.flaky, 1);
+        assert_eq!(summary.total(), 4);
+        assert_eq!(summary.execution_time, 3.0);
+        assert_eq!(summary.pass_rate(), Some(2.0 / 3.0));
+        assert_eq!(TestSummary::default().pass_rate(), None);
+    }
+
+    #[test]
+    fn should_summarize_test_results_by_suite() {
+        let test_results = vec![
+            TestResult::builder()
+                .with_suite_name("Suite.A".to_string())
+                .with_status(TestStatus::Passed)
+                .with_execution_time(1.5)
+                .build(),
+            TestResult::builder()
+                .with_suite_name("Suite.B".to_string())
+                .with_status(TestStatus::Failed)
+                .with_execution_time(0.5)
+                .build(),
+            TestResult::builder()
+                .with_status(TestStatus::Skipped)
+                .build(),
+            TestResult::builder()
+                .with_suite_name("Suite.A".to_string())
+                .with_status(TestStatus::Passed)
+                .with_execution_time(1.0)
+                .build(),
+        ];
+
+        let suites = TestSummary::by_suite(test_results.as_slice());
+
+        assert_eq!(suites.len(), 2);
+        assert_eq!(suites[0].0, "Suite.A");
+        assert_eq!(suites[0].1.passed, 2);
+        assert_eq!(suites[0].1.execution_time, 2.5);
+        assert_eq!(suites[1].0, "Suite.B");
+        assert_eq!(suites[1].1.failed, 1);
+    }
+}
@@ -0,0 +1,181 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::models::{test_result::TestResult, test_status::TestStatus};
+
+use super::TestParser;
+
+/// Struct that defines the parser of the events printed by `go test -json`
+pub struct GoTestParser {
+    pub content: String,
+}
+
+impl GoTestParser {
+    pub fn from(content: String) -> Self {
+        Self { content }
+    }
+}
+
+/// A single event printed by `go test -json`, as described by `go doc test2json`
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct TestEvent {
+    action: String,
+    package: Option<String>,
+    test: Option<String>,
+    elapsed: Option<f32>,
+    output: Option<String>,
+}
+
+#[derive(Default)]
+struct TestRun {
+    status: Option<TestStatus>,
+    elapsed: Option<f32>,
+    output: String,
+}
+
+/// Logic that converts `go test -json` events into a list of `TestResult` domain instances. Tests
+/// are named after the `Test` field, subtests included, and grouped in suites by package.
+impl TestParser for GoTestParser {
+    fn parse(&self) -> Result<Vec<TestResult>> {
+        let mut runs: Vec<((Option<String>, String), TestRun)> = vec![];
+        let mut positions: HashMap<(Option<String>, String), usize> = HashMap::new();
+
+        // lines other than events, such as build errors, are passed through by go test
+        for (index, line) in self.content.lines().enumerate() {
+            if !line.trim_start().starts_with('{') {
+                continue;
+            }
+            let event: TestEvent = serde_json::from_str(line)
+                .with_context(|| format!("invalid go test event at line {}", index + 1))?;
+            // events without a test refer to the whole package
+            let Some(test) = event.test else {
+                continue;
+            };
+
+            let key = (event.package, test);
+            let position = *positions.entry(key.clone()).or_insert_with(|| {
+                runs.push((key, TestRun::default()));
+                runs.len() - 1
+            });
+            let run = &mut runs[position].1;
+
+            match event.action.as_str() {
+                "pass" => run.status = Some(TestStatus::Passed),
+                "fail" => run.status = Some(TestStatus::Failed),
+                "skip" => run.status = Some(TestStatus::Skipped),
+                "output" => run.output.push_str(&event.output.unwrap_or_default()),
+                _ => {}
+            }
+            if event.elapsed.is_some() {
+                run.elapsed = event.elapsed;
+            }
+        }
+
+        let test_results = runs
+            .into_iter()
+            .map(|((package, test), run)| {
+                // tests without a result were running when the test binary crashed
+                let status = run.status.unwrap_or(TestStatus::Failed);
+
+                let mut test_result_builder = TestResult::builder().with_name(test);
+                if let Some(package) = package {
+                    test_result_builder = test_result_builder.with_suite_name(package);
+                }
+                if let Some(elapsed) = run.elapsed {
+                    test_result_builder = test_result_builder.with_execution_time(elapsed);
+                }
+                if status == TestStatus::Failed {
+                    let message = failure_message(&run.output);
+                    if !message.is_empty() {
+                        test_result_builder = test_result_builder.with_failure_message(message);
+                    }
+                }
+
+                test_result_builder.with_status(status).build()
+            })
+            .collect();
+
+        Ok(test_results)
+    }
+}
+
+/// Yields the output of a failed test, without the lines that go test prints when tests start and end
+fn failure_message(output: &str) -> String {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with("=== ") && !l.starts_with("--- "))
+        .collect::<Vec<&str>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GoTestParser;
+    use crate::{models::test_status::TestStatus, parsers::TestParser};
+    use indoc::indoc;
+
+    #[test]
+    fn should_parse_go_test_events_into_test_results() {
+        let go_test_events = indoc! {r#"
+            {"Action":"start","Package":"example.com/auth"}
+            {"Action":"run","Package":"example.com/auth","Test":"TestLogin"}
+            {"Action":"output","Package":"example.com/auth","Test":"TestLogin","Output":"=== RUN   TestLogin\n"}
+            {"Action":"output","Package":"example.com/auth","Test":"TestLogin","Output":"    login_test.go:12: expected 200, got 401\n"}
+            {"Action":"output","Package":"example.com/auth","Test":"TestLogin","Output":"--- FAIL: TestLogin (0.12s)\n"}
+            {"Action":"fail","Package":"example.com/auth","Test":"TestLogin","Elapsed":0.12}
+            {"Action":"run","Package":"example.com/auth","Test":"TestLogout"}
+            {"Action":"run","Package":"example.com/auth","Test":"TestLogout/expired"}
+            {"Action":"pass","Package":"example.com/auth","Test":"TestLogout/expired","Elapsed":0.01}
+            {"Action":"pass","Package":"example.com/auth","Test":"TestLogout","Elapsed":0.02}
+            {"Action":"run","Package":"example.com/auth","Test":"TestSignup"}
+            {"Action":"skip","Package":"example.com/auth","Test":"TestSignup","Elapsed":0}
+            {"Action":"fail","Package":"example.com/auth","Elapsed":0.3}
+        "#};
+
+        let test_results = GoTestParser::from(go_test_events.to_string())
+            .parse()
+            .expect("Unable to parse test results content");
+
+        assert_eq!(test_results.len(), 4);
+
+        let login = &test_results[0];
+        assert_eq!(login.name, "TestLogin");
+        assert_eq!(login.suite_name, Some("example.com/auth".to_string()));
+        assert_eq!(login.status, TestStatus::Failed);
+        assert_eq!(
+            login.failure_message,
+            Some("login_test.go:12: expected 200, got 401".to_string())
+        );
+        assert_eq!(login.execution_time, Some(0.12));
+
+        assert_eq!(test_results[1].name, "TestLogout");
+        assert_eq!(test_results[1].status, TestStatus::Passed);
+        assert_eq!(test_results[2].name, "TestLogout/expired");
+        assert_eq!(test_results[2].status, TestStatus::Passed);
+        assert_eq!(test_results[3].name, "TestSignup");
+        assert_eq!(test_results[3].status, TestStatus::Skipped);
+    }
+
+    #[test]
+    fn should_report_tests_without_result_as_failed() {
+        let go_test_events = indoc! {r#"
+            {"Action":"run","Package":"example.com/auth","Test":"TestPanic"}
+            {"Action":"output","Package":"example.com/auth","Test":"TestPanic","Output":"panic: runtime error: index out of range [1] with length 1\n"}
+            FAIL	example.com/auth	0.010s
+        "#};
+
+        let test_results = GoTestParser::from(go_test_events.to_string())
+            .parse()
+            .expect("Unable to parse test results content");
+
+        assert_eq!(test_results.len(), 1);
+        assert_eq!(test_results[0].status, TestStatus::Failed);
+        assert_eq!(
+            test_results[0].failure_message,
+            Some("panic: runtime error: index out of range [1] with length 1".to_string())
+        );
+    }
+}
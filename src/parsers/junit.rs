@@ -2,7 +2,10 @@ use anyhow::Result;
 use core::f32;
 use roxmltree::{Document, Node};
 
-use crate::models::{test_result::TestResult, test_status::TestStatus};
+use crate::{
+    error::TestvoxError,
+    models::{test_result::TestResult, test_status::TestStatus},
+};
 
 use super::TestParser;
 
@@ -21,6 +24,12 @@ impl JunitTestParser {
 impl TestParser for JunitTestParser {
     fn parse(&self) -> Result<Vec<TestResult>> {
         let doc = Document::parse(self.content.as_str())?;
+        if !matches!(
+            doc.root_element().tag_name().name(),
+            "testsuites" | "testsuite"
+        ) {
+            return Err(TestvoxError::UnknownFormat.into());
+        }
 
         let mut test_results: Vec<TestResult> = vec![];
 
@@ -45,6 +54,23 @@ impl TestParser for JunitTestParser {
                             test_result_builder.clone().with_suite_name(suite_name);
                     }
 
+                    if let Some(classname) = n.attribute("classname").map(|s| s.to_string()) {
+                        test_result_builder = test_result_builder.clone().with_classname(classname);
+                    }
+
+                    if let Some(file) = n.attribute("file").map(|s| s.to_string()) {
+                        test_result_builder = test_result_builder.clone().with_file(file);
+                    }
+
+                    // lines start at 1, so 0 stands for an unknown line
+                    if let Some(line) = n
+                        .attribute("line")
+                        .and_then(|l| l.parse::<u32>().ok())
+                        .filter(|l| *l > 0)
+                    {
+                        test_result_builder = test_result_builder.clone().with_line(line);
+                    }
+
                     if let Some(failure) = n.children().find(|n| n.has_tag_name("failure")) {
                         test_result_builder = test_result_builder
                             .clone()
@@ -55,6 +81,17 @@ impl TestParser for JunitTestParser {
                                     .unwrap_or("⚠️ missing test name")
                                     .to_string(),
                             );
+
+                        let stack_trace: String = failure
+                            .descendants()
+                            .filter(|n| n.is_text())
+                            .filter_map(|n| n.text())
+                            .collect();
+                        if !stack_trace.trim().is_empty() {
+                            test_result_builder = test_result_builder
+                                .clone()
+                                .with_stack_trace(stack_trace.trim().to_string());
+                        }
                     } else if n.children().any(|n| n.has_tag_name("skipped")) {
                         test_result_builder =
                             test_result_builder.clone().with_status(TestStatus::Skipped);
@@ -63,6 +100,13 @@ impl TestParser for JunitTestParser {
                             test_result_builder.clone().with_status(TestStatus::Passed);
                     };
 
+                    // Surefire records the failed runs of tests that passed when rerun
+                    if n.children()
+                        .any(|n| n.has_tag_name("flakyFailure") || n.has_tag_name("flakyError"))
+                    {
+                        test_result_builder = test_result_builder.clone().with_flaky(true);
+                    }
+
                     if let Some(execution_time) = n.attribute("time") {
                         let _ = execution_time.parse::<f32>().map(|t| {
                             test_result_builder = test_result_builder.clone().with_execution_time(t)
@@ -139,11 +183,13 @@ mod tests {
             .find(|t| t.name == "testCase9")
             .expect("Failed to find expected test");
         assert_eq!(first.suite_name, Some("Tests.Authentication".to_string()));
+        assert_eq!(first.classname, Some("Tests.Authentication".to_string()));
         assert!(matches!(first.status, TestStatus::Failed,));
         assert_eq!(
             first.failure_message,
             Some("Assertion error message".to_string())
         );
+        assert!(first.stack_trace.is_none());
         assert_eq!(first.execution_time, Some(0.982));
 
         let second = test_results
@@ -156,6 +202,7 @@ mod tests {
         );
         assert!(matches!(second.status, TestStatus::Skipped,));
         assert!(second.failure_message.is_none());
+        assert!(second.stack_trace.is_none());
         assert!(second.execution_time.is_none());
 
         let third = test_results
@@ -163,8 +210,74 @@ mod tests {
             .find(|t| t.name == "testCase1")
             .expect("Failed to find expected test");
         assert_eq!(third.suite_name, Some("Tests.Registration".to_string()));
+        assert!(third.file.is_none());
+        assert!(third.line.is_none());
         assert!(matches!(third.status, TestStatus::Passed,));
         assert!(third.failure_message.is_none());
         assert_eq!(third.execution_time, Some(2.113871));
+        assert!(!third.flaky);
+    }
+
+    #[test]
+    fn should_parse_stack_traces_of_failures() {
+        let junit_test_results_contents = indoc! {"
+            <testsuite name=\"Tests.Authentication\">
+                <testcase name=\"testCase9\">
+                    <failure message=\"Assertion error message\" type=\"AssertionError\">
+                        <!-- Call stack printed here -->
+                        at Tests.Authentication.testCase9(auth.rs:42)
+                    </failure>
+                </testcase>
+            </testsuite>"};
+
+        let test_results = JunitTestParser::from(junit_test_results_contents.to_string())
+            .parse()
+            .expect("Unable to parse test results content");
+
+        assert_eq!(
+            test_results[0].stack_trace,
+            Some("at Tests.Authentication.testCase9(auth.rs:42)".to_string())
+        );
+    }
+
+    #[test]
+    fn should_parse_source_file_and_line_of_tests() {
+        let junit_test_results_contents = indoc! {"
+            <testsuite name=\"Tests.Authentication\">
+                <testcase name=\"testCase9\" file=\"tests/auth.rs\" line=\"42\">
+                    <failure message=\"Assertion error message\"/>
+                </testcase>
+                <testcase name=\"testCase10\" file=\"tests/auth.rs\" line=\"not a line\"/>
+                <testcase name=\"testCase11\" file=\"tests/auth.rs\" line=\"0\"/>
+            </testsuite>"};
+
+        let test_results = JunitTestParser::from(junit_test_results_contents.to_string())
+            .parse()
+            .expect("Unable to parse test results content");
+
+        assert_eq!(test_results.len(), 3);
+        assert_eq!(test_results[0].file, Some("tests/auth.rs".to_string()));
+        assert_eq!(test_results[0].line, Some(42));
+        assert_eq!(test_results[1].file, Some("tests/auth.rs".to_string()));
+        assert!(test_results[1].line.is_none());
+        assert!(test_results[2].line.is_none());
+    }
+
+    #[test]
+    fn should_parse_tests_that_passed_when_rerun_as_flaky() {
+        let junit_test_results_contents = indoc! {"
+            <testsuite name=\"Tests.Registration\">
+                <testcase name=\"testCase1\" classname=\"Tests.Registration\" time=\"2.1\">
+                    <flakyFailure message=\"connection reset\" type=\"IOException\"/>
+                </testcase>
+            </testsuite>"};
+
+        let test_results = JunitTestParser::from(junit_test_results_contents.to_string())
+            .parse()
+            .expect("Unable to parse test results content");
+
+        assert_eq!(test_results.len(), 1);
+        assert!(matches!(test_results[0].status, TestStatus::Passed));
+        assert!(test_results[0].flaky);
     }
 }
@@ -0,0 +1,132 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::models::{test_result::TestResult, test_status::TestStatus};
+
+use super::TestParser;
+
+/// Struct that defines the parser of the libtest JSON events, printed by `cargo test` with
+/// `--format json` and by `cargo nextest run` with `--message-format libtest-json`
+pub struct LibtestParser {
+    pub content: String,
+}
+
+impl LibtestParser {
+    pub fn from(content: String) -> Self {
+        Self { content }
+    }
+}
+
+/// A single libtest JSON event
+#[derive(Deserialize)]
+struct TestEvent {
+    #[serde(rename = "type")]
+    kind: String,
+    event: String,
+    name: Option<String>,
+    exec_time: Option<f32>,
+    stdout: Option<String>,
+    message: Option<String>,
+}
+
+/// Logic that converts libtest JSON events into a list of `TestResult` domain instances. nextest
+/// prefixes test names with the binary they belong to, as `crate::binary$path::to::test`, which
+/// is used as suite name.
+impl TestParser for LibtestParser {
+    fn parse(&self) -> Result<Vec<TestResult>> {
+        let mut test_results: Vec<TestResult> = vec![];
+
+        for (index, line) in self.content.lines().enumerate() {
+            if !line.trim_start().starts_with('{') {
+                continue;
+            }
+            let event: TestEvent = serde_json::from_str(line)
+                .with_context(|| format!("invalid libtest event at line {}", index + 1))?;
+            if event.kind != "test" {
+                continue;
+            }
+
+            let status = match event.event.as_str() {
+                "ok" => TestStatus::Passed,
+                "failed" | "timeout" => TestStatus::Failed,
+                "ignored" => TestStatus::Skipped,
+                _ => continue,
+            };
+
+            let name = event.name.unwrap_or("⚠️ missing test name".to_string());
+            let (suite_name, name) = match name.split_once('$') {
+                Some((binary, name)) => (Some(binary.to_string()), name.to_string()),
+                None => (None, name),
+            };
+
+            let mut test_result_builder = TestResult::builder().with_name(name.clone());
+            if let Some(suite_name) = suite_name {
+                test_result_builder = test_result_builder.with_suite_name(suite_name);
+            }
+            if let Some((classname, _)) = name.rsplit_once("::") {
+                test_result_builder = test_result_builder.with_classname(classname.to_string());
+            }
+            if let Some(exec_time) = event.exec_time {
+                test_result_builder = test_result_builder.with_execution_time(exec_time);
+            }
+            if status == TestStatus::Failed {
+                let message = event.stdout.or(event.message).unwrap_or_default();
+                if !message.trim().is_empty() {
+                    test_result_builder =
+                        test_result_builder.with_failure_message(message.trim().to_string());
+                }
+            }
+
+            test_results.push(test_result_builder.with_status(status).build());
+        }
+
+        Ok(test_results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LibtestParser;
+    use crate::{models::test_status::TestStatus, parsers::TestParser};
+    use indoc::indoc;
+
+    #[test]
+    fn should_parse_libtest_events_into_test_results() {
+        let libtest_events = indoc! {r#"
+            { "type": "suite", "event": "started", "test_count": 3 }
+            { "type": "test", "event": "started", "name": "testvox::bin/testvox$config::tests::should_parse" }
+            { "type": "test", "name": "testvox::bin/testvox$config::tests::should_parse", "event": "ok", "exec_time": 0.002 }
+            { "type": "test", "event": "started", "name": "testvox::bin/testvox$config::tests::should_fail" }
+            { "type": "test", "name": "testvox::bin/testvox$config::tests::should_fail", "event": "failed", "exec_time": 0.004, "stdout": "thread 'should_fail' panicked at src/config.rs:3:5:\nassertion failed\n" }
+            { "type": "test", "event": "ignored", "name": "slow_test" }
+            { "type": "suite", "event": "failed", "passed": 1, "failed": 1, "ignored": 1 }
+        "#};
+
+        let test_results = LibtestParser::from(libtest_events.to_string())
+            .parse()
+            .expect("Unable to parse test results content");
+
+        assert_eq!(test_results.len(), 3);
+
+        let passed = &test_results[0];
+        assert_eq!(passed.name, "config::tests::should_parse");
+        assert_eq!(passed.suite_name, Some("testvox::bin/testvox".to_string()));
+        assert_eq!(passed.classname, Some("config::tests".to_string()));
+        assert_eq!(passed.status, TestStatus::Passed);
+        assert_eq!(passed.execution_time, Some(0.002));
+
+        let failed = &test_results[1];
+        assert_eq!(failed.status, TestStatus::Failed);
+        assert_eq!(
+            failed.failure_message,
+            Some(
+                "thread 'should_fail' panicked at src/config.rs:3:5:\nassertion failed".to_string()
+            )
+        );
+
+        let ignored = &test_results[2];
+        assert_eq!(ignored.name, "slow_test");
+        assert!(ignored.suite_name.is_none());
+        assert_eq!(ignored.status, TestStatus::Skipped);
+    }
+}
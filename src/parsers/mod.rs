@@ -1,5 +1,9 @@
+/// module that includes models and logic to parse the events printed by `go test -json`
+pub mod gotest;
 /// module that includes models and logic to parse Junit results
 pub mod junit;
+/// module that includes models and logic to parse libtest JSON events, as printed by cargo test and nextest
+pub mod libtest;
 
 use crate::models::test_result::TestResult;
 use anyhow::Result;
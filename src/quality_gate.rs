@@ -0,0 +1,176 @@
+use serde::{de, Deserialize, Deserializer};
+use std::{fmt, str::FromStr};
+
+use crate::models::test_report::Report;
+
+/// Condition that fails a quality gate, each with its own exit code, so that pipelines can tell
+/// them apart. Exit codes 1 and 2 are left to errors and invalid arguments.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FailOn {
+    /// any test failed, exits with 3
+    Failures,
+    /// more than the given number of tests failed, exits with 4
+    FailuresAbove(usize),
+    /// the pass rate, skipped tests aside, is below the given percentage, exits with 5
+    PassRateBelow(f32),
+    /// no test was found, exits with 6
+    NoTests,
+    /// any test is flaky, exits with 7
+    Flaky,
+}
+
+impl FailOn {
+    /// The exit code of the condition
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            FailOn::Failures => 3,
+            FailOn::FailuresAbove(_) => 4,
+            FailOn::PassRateBelow(_) => 5,
+            FailOn::NoTests => 6,
+            FailOn::Flaky => 7,
+        }
+    }
+
+    /// Whether the given report meets the condition
+    pub fn is_met(&self, report: &Report) -> bool {
+        let summary = &report.summary;
+        match self {
+            FailOn::Failures => summary.failed > 0,
+            FailOn::FailuresAbove(max) => summary.failed > *max,
+            FailOn::PassRateBelow(percentage) => summary
+                .pass_rate()
+                .is_some_and(|rate| rate * 100.0 < *percentage),
+            FailOn::NoTests => summary.total() == 0,
+            FailOn::Flaky => summary.flaky > 0,
+        }
+    }
+}
+
+/// Yields the first of the given conditions that the report meets, if any
+pub fn check<'a>(conditions: &'a [FailOn], report: &Report) -> Option<&'a FailOn> {
+    conditions.iter().find(|c| c.is_met(report))
+}
+
+/// Formats the condition as it is parsed
+impl fmt::Display for FailOn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FailOn::Failures => write!(f, "failures"),
+            FailOn::FailuresAbove(max) => write!(f, "failures-above={}", max),
+            FailOn::PassRateBelow(percentage) => write!(f, "pass-rate-below={}", percentage),
+            FailOn::NoTests => write!(f, "no-tests"),
+            FailOn::Flaky => write!(f, "flaky"),
+        }
+    }
+}
+
+/// Parses conditions given as `failures`, `failures-above=N`, `pass-rate-below=X`, `no-tests`
+/// or `flaky`
+impl FromStr for FailOn {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('=') {
+            None if s == "failures" => Ok(FailOn::Failures),
+            None if s == "no-tests" => Ok(FailOn::NoTests),
+            None if s == "flaky" => Ok(FailOn::Flaky),
+            Some(("failures-above", max)) => max
+                .parse::<usize>()
+                .map(FailOn::FailuresAbove)
+                .map_err(|_| format!("invalid number of failures '{}'", max)),
+            Some(("pass-rate-below", percentage)) => match percentage.parse::<f32>() {
+                Ok(p) if (0.0..=100.0).contains(&p) => Ok(FailOn::PassRateBelow(p)),
+                _ => Err(format!("invalid percentage '{}'", percentage)),
+            },
+            _ => Err(format!(
+                "unknown condition '{}', expected one of: failures, failures-above=N, pass-rate-below=X, no-tests, flaky",
+                s
+            )),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for FailOn {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use crate::models::{
+        test_report::{Report, ReportBuilder},
+        test_result::TestResult,
+        test_status::TestStatus,
+    };
+
+    use super::{check, FailOn};
+
+    fn sample_report() -> Report {
+        let test_result = |name: &str, status: TestStatus| {
+            TestResult::builder()
+                .with_name(name.to_string())
+                .with_status(status)
+                .build()
+        };
+
+        ReportBuilder::new()
+            .with_test_results(vec![
+                test_result("a", TestStatus::Passed),
+                test_result("b", TestStatus::Passed),
+                test_result("c", TestStatus::Passed),
+                test_result("d", TestStatus::Failed),
+                test_result("e", TestStatus::Skipped),
+            ])
+            .build::<Report>()
+    }
+
+    #[test_case("failures", Some(FailOn::Failures) ; "any failure")]
+    #[test_case("failures-above=2", Some(FailOn::FailuresAbove(2)) ; "failures above")]
+    #[test_case("pass-rate-below=99.5", Some(FailOn::PassRateBelow(99.5)) ; "pass rate below")]
+    #[test_case("no-tests", Some(FailOn::NoTests) ; "no tests")]
+    #[test_case("flaky", Some(FailOn::Flaky) ; "flaky")]
+    #[test_case("failures-above=-1", None ; "negative failures")]
+    #[test_case("pass-rate-below=101", None ; "invalid percentage")]
+    #[test_case("errors", None ; "unknown condition")]
+    fn should_parse_conditions(condition: &str, expected: Option<FailOn>) {
+        let parsed = condition.parse::<FailOn>().ok();
+
+        assert_eq!(parsed, expected);
+        if let Some(parsed) = parsed {
+            assert_eq!(parsed.to_string(), condition);
+        }
+    }
+
+    #[test_case(FailOn::Failures, true ; "any failure")]
+    #[test_case(FailOn::FailuresAbove(1), false ; "failures above")]
+    #[test_case(FailOn::PassRateBelow(80.0), true ; "pass rate below")]
+    #[test_case(FailOn::PassRateBelow(75.0), false ; "pass rate at threshold")]
+    #[test_case(FailOn::NoTests, false ; "no tests")]
+    #[test_case(FailOn::Flaky, false ; "flaky")]
+    fn should_check_conditions(condition: FailOn, expected: bool) {
+        assert_eq!(condition.is_met(&sample_report()), expected);
+    }
+
+    #[test]
+    fn should_yield_the_first_condition_met() {
+        let conditions = vec![
+            FailOn::NoTests,
+            FailOn::PassRateBelow(80.0),
+            FailOn::Failures,
+        ];
+
+        let met = check(&conditions, &sample_report());
+
+        assert_eq!(met, Some(&FailOn::PassRateBelow(80.0)));
+        assert_eq!(met.unwrap().exit_code(), 5);
+        assert_eq!(check(&[], &sample_report()), None);
+    }
+}
@@ -0,0 +1,175 @@
+use crate::models::{
+    test_report::{PrettyPrint, ReportBuilder},
+    test_summary::TestSummary,
+};
+
+use super::html::escape_html;
+
+/// Horizontal padding around each side of the badge texts, in pixels
+const PADDING: u32 = 6;
+
+/// Struct that defines a shields-style SVG status badge, rendered offline
+pub struct BadgeReport {
+    /// text of the left side of the badge
+    pub label: String,
+    /// counters over all the parsed test results
+    pub summary: TestSummary,
+}
+
+impl BadgeReport {
+    /// Overrides the default "tests" label
+    pub fn with_label(mut self, label: String) -> BadgeReport {
+        self.label = label;
+        self
+    }
+
+    /// Utility that yields the text of the right side of the badge
+    pub fn message(&self) -> String {
+        if self.summary.total() == 0 {
+            return "no tests".to_string();
+        }
+
+        let mut parts = vec![format!("{} passed", self.summary.passed)];
+        if self.summary.failed > 0 {
+            parts.push(format!("{} failed", self.summary.failed));
+        }
+        if self.summary.skipped > 0 {
+            parts.push(format!("{} skipped", self.summary.skipped));
+        }
+        parts.join(", ")
+    }
+
+    /// Utility that yields the color of the right side of the badge, by outcome
+    pub fn color(&self) -> &'static str {
+        if self.summary.failed > 0 {
+            "#e05d44"
+        } else if self.summary.passed > 0 {
+            "#4c1"
+        } else {
+            "#9f9f9f"
+        }
+    }
+}
+
+/// Turns test results into a badge
+impl From<ReportBuilder> for BadgeReport {
+    fn from(value: ReportBuilder) -> Self {
+        BadgeReport {
+            label: "tests".to_string(),
+            summary: value.summary,
+        }
+    }
+}
+
+/// Pretty print implementation for the badge type, yielding an SVG document
+impl PrettyPrint for BadgeReport {
+    fn to_string_pretty(&self) -> String {
+        let message = self.message();
+        let label_width = text_width(&self.label) + 2 * PADDING;
+        let message_width = text_width(&message) + 2 * PADDING;
+        let width = label_width + message_width;
+
+        let label = escape_html(&self.label);
+        let message = escape_html(&message);
+        // texts are drawn at 10x scale, as shields.io does, for a more precise positioning
+        let label_x = label_width * 5;
+        let message_x = (label_width + message_width / 2) * 10;
+
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"20\" role=\"img\" aria-label=\"{label}: {message}\">\
+             <title>{label}: {message}</title>\
+             <linearGradient id=\"s\" x2=\"0\" y2=\"100%\"><stop offset=\"0\" stop-color=\"#bbb\" stop-opacity=\".1\"/><stop offset=\"1\" stop-opacity=\".1\"/></linearGradient>\
+             <clipPath id=\"r\"><rect width=\"{width}\" height=\"20\" rx=\"3\" fill=\"#fff\"/></clipPath>\
+             <g clip-path=\"url(#r)\">\
+             <rect width=\"{label_width}\" height=\"20\" fill=\"#555\"/>\
+             <rect x=\"{label_width}\" width=\"{message_width}\" height=\"20\" fill=\"{color}\"/>\
+             <rect width=\"{width}\" height=\"20\" fill=\"url(#s)\"/>\
+             </g>\
+             <g fill=\"#fff\" text-anchor=\"middle\" font-family=\"Verdana,Geneva,DejaVu Sans,sans-serif\" text-rendering=\"geometricPrecision\" font-size=\"110\">\
+             <text aria-hidden=\"true\" x=\"{label_x}\" y=\"150\" fill=\"#010101\" fill-opacity=\".3\" transform=\"scale(.1)\">{label}</text>\
+             <text x=\"{label_x}\" y=\"140\" transform=\"scale(.1)\">{label}</text>\
+             <text aria-hidden=\"true\" x=\"{message_x}\" y=\"150\" fill=\"#010101\" fill-opacity=\".3\" transform=\"scale(.1)\">{message}</text>\
+             <text x=\"{message_x}\" y=\"140\" transform=\"scale(.1)\">{message}</text>\
+             </g>\
+             </svg>\n",
+            color = self.color(),
+        )
+    }
+}
+
+/// Approximates the width of a text rendered in 11px Verdana, the font used by shields-style
+/// badges, without relying on font metrics being available
+fn text_width(text: &str) -> u32 {
+    text.chars()
+        .map(|c| match c {
+            'i' | 'j' | 'l' | '.' | ',' | ':' | ';' | '\'' | '|' | '!' => 3.5,
+            'f' | 'r' | 't' | ' ' | '(' | ')' | '[' | ']' | '-' | 'I' => 4.5,
+            'm' | 'w' | 'M' | 'W' | '%' => 10.5,
+            '0'..='9' => 7.0,
+            c if c.is_uppercase() => 7.5,
+            _ => 6.5,
+        })
+        .sum::<f32>()
+        .ceil() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{
+        test_report::{PrettyPrint, ReportBuilder},
+        test_result::TestResult,
+        test_status::TestStatus,
+    };
+    use test_case::test_case;
+
+    use super::BadgeReport;
+
+    fn test_results(passed: usize, failed: usize, skipped: usize) -> Vec<TestResult> {
+        let with_status = |status: TestStatus, count: usize| {
+            (0..count).map(move |i| {
+                TestResult::builder()
+                    .with_name(format!("{:?}-{}", status, i))
+                    .with_status(status.clone())
+                    .build()
+            })
+        };
+        with_status(TestStatus::Passed, passed)
+            .chain(with_status(TestStatus::Failed, failed))
+            .chain(with_status(TestStatus::Skipped, skipped))
+            .collect()
+    }
+
+    #[test_case(412, 3, 0, "412 passed, 3 failed", "#e05d44" ; "with failures")]
+    #[test_case(412, 0, 2, "412 passed, 2 skipped", "#4c1" ; "without failures")]
+    #[test_case(0, 0, 2, "0 passed, 2 skipped", "#9f9f9f" ; "only skipped")]
+    #[test_case(0, 0, 0, "no tests", "#9f9f9f" ; "no tests")]
+    fn should_create_badge_colored_by_outcome(
+        passed: usize,
+        failed: usize,
+        skipped: usize,
+        expected_message: &str,
+        expected_color: &str,
+    ) {
+        let badge = ReportBuilder::new()
+            .with_test_results(test_results(passed, failed, skipped))
+            .build::<BadgeReport>();
+
+        assert_eq!(badge.message(), expected_message);
+        assert_eq!(badge.color(), expected_color);
+    }
+
+    #[test]
+    fn should_render_badge_as_svg() {
+        let svg = ReportBuilder::new()
+            .with_test_results(test_results(412, 3, 0))
+            .build::<BadgeReport>()
+            .with_label("e2e <tests>".to_string())
+            .to_string_pretty();
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.contains("<title>e2e &lt;tests&gt;: 412 passed, 3 failed</title>"));
+        assert!(svg.contains("fill=\"#e05d44\""));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert!(roxmltree::Document::parse(&svg).is_ok());
+    }
+}
@@ -0,0 +1,153 @@
+use crate::models::test_report::{PrettyPrint, ReportBuilder};
+use serde::Serialize;
+use url::Url;
+
+/// Enum that defines a DingTalk custom robot message. DingTalk requires a button on `actionCard`
+/// messages, hence reports without a link fall back to a plain `markdown` message.
+#[derive(Serialize)]
+#[serde(tag = "msgtype")]
+pub enum DingTalkReport {
+    #[serde(rename = "actionCard")]
+    ActionCard {
+        #[serde(rename = "actionCard")]
+        action_card: ActionCard,
+    },
+    #[serde(rename = "markdown")]
+    Markdown { markdown: Markdown },
+}
+
+/// Struct that defines an action card with a single button
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionCard {
+    pub title: String,
+    pub text: String,
+    pub btn_orientation: String,
+    pub single_title: String,
+    #[serde(rename = "singleURL")]
+    pub single_url: Url,
+}
+
+/// Struct that defines a markdown message
+#[derive(Serialize)]
+pub struct Markdown {
+    pub title: String,
+    pub text: String,
+}
+
+/// Pretty print implementation for the DingTalk report type
+impl PrettyPrint for DingTalkReport {
+    fn to_string_pretty(&self) -> String {
+        serde_json::to_string_pretty(&self).expect("unable to serialize report to JSON")
+    }
+}
+
+/// Turns test results into a DingTalk report
+impl From<ReportBuilder> for DingTalkReport {
+    fn from(value: ReportBuilder) -> Self {
+        // DingTalk only breaks lines on empty lines
+        let mut lines = vec![
+            format!("### {}", value.title),
+            format!(
+                "{} failed, {} passed, {} skipped",
+                value.summary.failed, value.summary.passed, value.summary.skipped
+            ),
+        ];
+
+        if value.test_results.is_empty() {
+            lines.push("⚠️ unable to find test results".to_string());
+        } else {
+            lines.extend(value.test_results.iter().map(|t| t.to_commonmark_string()));
+        }
+
+        let text = lines.join("\n\n");
+
+        match value.link {
+            Some(link) => DingTalkReport::ActionCard {
+                action_card: ActionCard {
+                    title: value.title,
+                    text,
+                    btn_orientation: "0".to_string(),
+                    single_title: "View details".to_string(),
+                    single_url: link,
+                },
+            },
+            None => DingTalkReport::Markdown {
+                markdown: Markdown {
+                    title: value.title,
+                    text,
+                },
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{
+        test_report::{PrettyPrint, ReportBuilder},
+        test_result::TestResult,
+        test_status::TestStatus,
+    };
+    use assert_json::assert_json;
+    use url::Url;
+
+    use super::DingTalkReport;
+
+    fn test_failed() -> TestResult {
+        TestResult::builder()
+            .with_name("a test failed".to_string())
+            .with_status(TestStatus::Failed)
+            .with_failure_message("A failure".to_string())
+            .with_execution_time(1.2)
+            .build()
+    }
+
+    #[test]
+    fn should_create_report_in_dingtalk_action_card_format() {
+        let report = ReportBuilder::new()
+            .with_title("A DingTalk report".to_string())
+            .with_test_results(vec![test_failed()])
+            .with_link(Url::parse("http://localhost/run/123").expect("unable to parse url"))
+            .build::<DingTalkReport>();
+
+        let expected_text = format!(
+            "### A DingTalk report\n\n1 failed, 0 passed, 0 skipped\n\n{}",
+            test_failed().to_commonmark_string()
+        );
+
+        assert_json!(report.to_string_pretty().as_str(), {
+                "msgtype": "actionCard",
+                "actionCard": {
+                    "title": "A DingTalk report",
+                    "text": expected_text.as_str(),
+                    "btnOrientation": "0",
+                    "singleTitle": "View details",
+                    "singleURL": "http://localhost/run/123"
+                }
+            }
+        );
+    }
+
+    #[test]
+    fn should_create_report_in_dingtalk_markdown_format_without_link() {
+        let report = ReportBuilder::new()
+            .with_title("A DingTalk report".to_string())
+            .with_test_results(vec![test_failed()])
+            .build::<DingTalkReport>();
+
+        let expected_text = format!(
+            "### A DingTalk report\n\n1 failed, 0 passed, 0 skipped\n\n{}",
+            test_failed().to_commonmark_string()
+        );
+
+        assert_json!(report.to_string_pretty().as_str(), {
+                "msgtype": "markdown",
+                "markdown": {
+                    "title": "A DingTalk report",
+                    "text": expected_text.as_str()
+                }
+            }
+        );
+    }
+}
@@ -0,0 +1,445 @@
+use crate::models::{
+    test_report::{PrettyPrint, ReportBuilder},
+    test_result::TestResult,
+    test_status::TestStatus,
+    test_summary::TestSummary,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use url::Url;
+
+use super::html::escape_html;
+
+/// Default subject of the email. Supported placeholders are `{title}`, `{total}`, `{passed}`,
+/// `{failed}` and `{skipped}`
+pub const DEFAULT_SUBJECT_TEMPLATE: &str =
+    "{title}: {failed} failed, {passed} passed, {skipped} skipped";
+
+/// Boundary between the parts of the multipart message. Parts are base64 encoded, so it can never
+/// clash with their contents.
+const BOUNDARY: &str = "testvox-alternative-boundary";
+
+/// Struct that defines an email report, rendered as a MIME `multipart/alternative` message
+pub struct EmailReport {
+    /// the title of the report
+    pub title: String,
+    /// counters over all the parsed test results
+    pub summary: TestSummary,
+    /// the reported test results
+    pub test_results: Vec<TestResult>,
+    /// optional link to view more details related to the report
+    pub link: Option<Url>,
+    /// template used to generate the subject of the email
+    pub subject_template: String,
+}
+
+/// Turns test results into an email report
+impl From<ReportBuilder> for EmailReport {
+    fn from(value: ReportBuilder) -> Self {
+        EmailReport {
+            title: value.title,
+            summary: value.summary,
+            test_results: value.test_results,
+            link: value.link,
+            subject_template: DEFAULT_SUBJECT_TEMPLATE.to_string(),
+        }
+    }
+}
+
+impl EmailReport {
+    /// Overrides the default subject template. See `DEFAULT_SUBJECT_TEMPLATE` for the supported
+    /// placeholders
+    pub fn with_subject_template(mut self, subject_template: String) -> EmailReport {
+        self.subject_template = subject_template;
+        self
+    }
+
+    /// Utility that renders the subject template
+    pub fn subject(&self) -> String {
+        self.subject_template
+            .replace("{title}", &self.title)
+            .replace("{total}", &self.summary.total().to_string())
+            .replace("{passed}", &self.summary.passed.to_string())
+            .replace("{failed}", &self.summary.failed.to_string())
+            .replace("{skipped}", &self.summary.skipped.to_string())
+    }
+
+    /// Utility that renders the `text/plain` alternative of the email
+    pub fn to_plain_text(&self) -> String {
+        let mut text = format!(
+            "{}\n{}\n\n{} failed, {} passed, {} skipped ({}s)\n\n",
+            self.title,
+            "=".repeat(self.title.chars().count()),
+            self.summary.failed,
+            self.summary.passed,
+            self.summary.skipped,
+            self.summary.execution_time
+        );
+
+        if self.test_results.is_empty() {
+            text.push_str("unable to find test results\n");
+        }
+
+        for t in self.test_results.iter() {
+            let status = t.status.to_string().to_uppercase();
+            match t.execution_time {
+                Some(time) => text.push_str(&format!("{:<8}{} ({}s)\n", status, t.name, time)),
+                None => text.push_str(&format!("{:<8}{}\n", status, t.name)),
+            }
+            if let Some(failure_message) = &t.failure_message {
+                failure_message
+                    .lines()
+                    .for_each(|l| text.push_str(&format!("        {}\n", l)));
+            }
+        }
+
+        if let Some(link) = &self.link {
+            text.push_str(&format!("\nView details: {}\n", link));
+        }
+
+        text
+    }
+
+    /// Utility that renders the `text/html` alternative of the email. Layout relies on tables and
+    /// inline styles only, as most desktop email clients (Outlook above all) ignore stylesheets.
+    pub fn to_html(&self) -> String {
+        let rows: String = if self.test_results.is_empty() {
+            row("⚠️ unable to find test results".to_string())
+        } else {
+            self.test_results
+                .iter()
+                .map(|t| row(test_to_html(t)))
+                .collect()
+        };
+
+        let link = self
+            .link
+            .as_ref()
+            .map(|l| {
+                row(format!(
+                    "<a href=\"{}\" style=\"color:#0969da;font-weight:bold;\">View details</a>",
+                    escape_html(l.as_str())
+                ))
+            })
+            .unwrap_or_default();
+
+        format!(
+            "<!DOCTYPE html>\n\
+             <html>\n\
+             <head><meta http-equiv=\"Content-Type\" content=\"text/html; charset=utf-8\"><title>{title}</title></head>\n\
+             <body style=\"margin:0;padding:0;background-color:#f6f8fa;\">\n\
+             <table role=\"presentation\" width=\"100%\" cellpadding=\"0\" cellspacing=\"0\" border=\"0\" bgcolor=\"#f6f8fa\"><tr><td align=\"center\" style=\"padding:24px;\">\n\
+             <table role=\"presentation\" width=\"640\" cellpadding=\"0\" cellspacing=\"0\" border=\"0\" bgcolor=\"#ffffff\" style=\"border:1px solid #d0d7de;font-family:Arial,Helvetica,sans-serif;font-size:14px;color:#1f2328;\">\n\
+             <tr><td style=\"padding:16px 24px;font-size:20px;font-weight:bold;\">{title}</td></tr>\n\
+             <tr><td style=\"padding:0 24px 16px 24px;\">\
+             <span style=\"color:#cf222e;font-weight:bold;\">{failed} failed</span>, \
+             <span style=\"color:#1a7f37;font-weight:bold;\">{passed} passed</span>, \
+             <span style=\"color:#9a6700;font-weight:bold;\">{skipped} skipped</span> \
+             <span style=\"color:#656d76;\">({time}s)</span></td></tr>\n\
+             {rows}{link}\
+             </table>\n\
+             </td></tr></table>\n\
+             </body>\n\
+             </html>\n",
+            title = escape_html(&self.title),
+            failed = self.summary.failed,
+            passed = self.summary.passed,
+            skipped = self.summary.skipped,
+            time = self.summary.execution_time,
+        )
+    }
+}
+
+/// Pretty print implementation for the email report type, yielding a message that can be piped
+/// to `sendmail`
+impl PrettyPrint for EmailReport {
+    fn to_string_pretty(&self) -> String {
+        format!(
+            "MIME-Version: 1.0\r\n\
+             Subject: {subject}\r\n\
+             Content-Type: multipart/alternative; boundary=\"{BOUNDARY}\"\r\n\
+             \r\n\
+             --{BOUNDARY}\r\n\
+             Content-Type: text/plain; charset=utf-8\r\n\
+             Content-Transfer-Encoding: base64\r\n\
+             \r\n\
+             {text}\r\n\
+             --{BOUNDARY}\r\n\
+             Content-Type: text/html; charset=utf-8\r\n\
+             Content-Transfer-Encoding: base64\r\n\
+             \r\n\
+             {html}\r\n\
+             --{BOUNDARY}--\r\n",
+            subject = encode_header(&self.subject()),
+            text = encode_body(&self.to_plain_text()),
+            html = encode_body(&self.to_html()),
+        )
+    }
+}
+
+fn row(content: String) -> String {
+    format!(
+        "<tr><td style=\"padding:8px 24px;border-top:1px solid #eaeef2;\">{content}</td></tr>\n"
+    )
+}
+
+fn test_to_html(test_result: &TestResult) -> String {
+    let color = match test_result.status {
+        TestStatus::Failed => "#cf222e",
+        TestStatus::Passed => "#1a7f37",
+        TestStatus::Skipped => "#9a6700",
+    };
+    let time = test_result
+        .execution_time
+        .map(|t| {
+            format!(" <span style=\"color:#656d76;font-family:Consolas,'Courier New',monospace;\">({t}s)</span>")
+        })
+        .unwrap_or_default();
+    let failure = test_result
+        .failure_message
+        .as_ref()
+        .map(|m| {
+            format!(
+                "<pre style=\"margin:8px 0 0 0;padding:8px;background-color:#f6f8fa;font-family:Consolas,'Courier New',monospace;font-size:12px;white-space:pre-wrap;\">{}</pre>",
+                escape_html(m)
+            )
+        })
+        .unwrap_or_default();
+
+    format!(
+        "<span style=\"color:{color};font-weight:bold;\">{status}</span> {name}{time}{failure}",
+        status = test_result.status.to_string().to_uppercase(),
+        name = escape_html(&test_result.name),
+    )
+}
+
+/// Encodes a body part in base64, wrapping lines at 76 characters as required by RFC 2045
+fn encode_body(value: &str) -> String {
+    STANDARD
+        .encode(value)
+        .as_bytes()
+        .chunks(76)
+        .map(|chunk| String::from_utf8_lossy(chunk).to_string())
+        .collect::<Vec<String>>()
+        .join("\r\n")
+}
+
+/// Encodes header values on a single logical line, with line breaks and other control characters
+/// replaced by spaces so that they can't add headers. Non-ASCII values are encoded as RFC 2047
+/// encoded-words, folded so that each one stays below the 75 characters limit, while ASCII values
+/// are folded at spaces so that lines stay below the 78 characters limit
+fn encode_header(value: &str) -> String {
+    let value: String = value
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect();
+
+    if value.is_ascii() {
+        let mut lines: Vec<String> = vec![];
+        let mut line = String::new();
+        for word in value.split(' ') {
+            if !line.is_empty() && line.len() + 1 + word.len() > 68 {
+                lines.push(line);
+                line = word.to_string();
+            } else if line.is_empty() && lines.is_empty() {
+                line.push_str(word);
+            } else {
+                line.push(' ');
+                line.push_str(word);
+            }
+        }
+        lines.push(line);
+
+        return lines.join("\r\n ");
+    }
+
+    let mut words: Vec<String> = vec![];
+    let mut chunk = String::new();
+    for c in value.chars() {
+        if chunk.len() + c.len_utf8() > 45 {
+            words.push(format!("=?UTF-8?B?{}?=", STANDARD.encode(&chunk)));
+            chunk.clear();
+        }
+        chunk.push(c);
+    }
+    words.push(format!("=?UTF-8?B?{}?=", STANDARD.encode(&chunk)));
+
+    words.join("\r\n ")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{
+        test_report::{PrettyPrint, ReportBuilder},
+        test_result::TestResult,
+        test_status::TestStatus,
+    };
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use indoc::indoc;
+    use url::Url;
+
+    use super::{encode_header, EmailReport};
+
+    fn sample_report() -> EmailReport {
+        ReportBuilder::new()
+            .with_title("Nightly <regression>".to_string())
+            .include_skipped()
+            .with_test_results(vec![
+                TestResult::builder()
+                    .with_name("a test passed".to_string())
+                    .with_status(TestStatus::Passed)
+                    .with_execution_time(3.3)
+                    .build(),
+                TestResult::builder()
+                    .with_name("a test skipped".to_string())
+                    .with_status(TestStatus::Skipped)
+                    .build(),
+                TestResult::builder()
+                    .with_name("a test failed".to_string())
+                    .with_status(TestStatus::Failed)
+                    .with_failure_message("expected 1\nbut was 2".to_string())
+                    .with_execution_time(1.2)
+                    .build(),
+            ])
+            .with_link(Url::parse("http://localhost/run/123").expect("unable to parse url"))
+            .build::<EmailReport>()
+    }
+
+    #[test]
+    fn should_render_subject_from_template() {
+        let report = sample_report();
+
+        assert_eq!(
+            report.subject(),
+            "Nightly <regression>: 1 failed, 1 passed, 1 skipped"
+        );
+        assert_eq!(
+            report
+                .with_subject_template("[{failed}/{total}] {title}".to_string())
+                .subject(),
+            "[1/3] Nightly <regression>"
+        );
+    }
+
+    #[test]
+    fn should_render_plain_text_alternative() {
+        assert_eq!(
+            sample_report().to_plain_text(),
+            indoc! {"
+                Nightly <regression>
+                ====================
+
+                1 failed, 1 passed, 1 skipped (4.5s)
+
+                FAILED  a test failed (1.2s)
+                        expected 1
+                        but was 2
+                SKIPPED a test skipped
+
+                View details: http://localhost/run/123
+            "}
+        );
+    }
+
+    #[test]
+    fn should_render_html_alternative_with_inline_styles() {
+        let html = sample_report().to_html();
+
+        assert!(html.contains("<title>Nightly &lt;regression&gt;</title>"));
+        assert!(html.contains(
+            "<span style=\"color:#cf222e;font-weight:bold;\">FAILED</span> a test failed"
+        ));
+        assert!(html.contains("expected 1\nbut was 2</pre>"));
+        assert!(html.contains("<a href=\"http://localhost/run/123\""));
+        assert!(!html.contains("<style"));
+        assert!(!html.contains("class="));
+    }
+
+    #[test]
+    fn should_render_multipart_alternative_message() {
+        let report = sample_report();
+        let message = report.to_string_pretty();
+
+        let (headers, body) = message
+            .split_once("\r\n\r\n")
+            .expect("missing headers separator");
+        assert_eq!(
+            headers,
+            "MIME-Version: 1.0\r\n\
+             Subject: Nightly <regression>: 1 failed, 1 passed, 1 skipped\r\n\
+             Content-Type: multipart/alternative; boundary=\"testvox-alternative-boundary\""
+        );
+
+        let parts: Vec<&str> = body
+            .split("--testvox-alternative-boundary")
+            .filter(|p| !p.trim().is_empty() && p.trim() != "--")
+            .collect();
+        assert_eq!(parts.len(), 2);
+        assert!(parts[0].starts_with("\r\nContent-Type: text/plain; charset=utf-8\r\n"));
+        assert!(parts[1].starts_with("\r\nContent-Type: text/html; charset=utf-8\r\n"));
+
+        let decode = |part: &str| {
+            let (_, encoded) = part.split_once("\r\n\r\n").expect("missing part body");
+            let bytes = STANDARD
+                .decode(encoded.replace("\r\n", ""))
+                .expect("invalid base64 body");
+            String::from_utf8(bytes).expect("invalid utf-8 body")
+        };
+        assert_eq!(decode(parts[0]), report.to_plain_text());
+        assert_eq!(decode(parts[1]), report.to_html());
+        assert!(body.lines().all(|l| l.trim_end_matches('\r').len() <= 76));
+    }
+
+    #[test]
+    fn should_encode_non_ascii_headers() {
+        assert_eq!(encode_header("plain subject"), "plain subject");
+
+        let encoded = encode_header(
+            "❌ 3 tests failed on the nightly regression run of the checkout service",
+        );
+        assert!(encoded
+            .split("\r\n ")
+            .all(|w| w.starts_with("=?UTF-8?B?") && w.ends_with("?=") && w.len() <= 75));
+        let decoded: String = encoded
+            .split("\r\n ")
+            .map(|w| {
+                let bytes = STANDARD
+                    .decode(w.trim_start_matches("=?UTF-8?B?").trim_end_matches("?="))
+                    .expect("invalid encoded word");
+                String::from_utf8(bytes).expect("encoded word splits a character")
+            })
+            .collect();
+        assert_eq!(
+            decoded,
+            "❌ 3 tests failed on the nightly regression run of the checkout service"
+        );
+    }
+
+    #[test]
+    fn should_keep_headers_on_a_single_logical_line() {
+        let encoded = encode_header("Nightly\r\nBcc: someone@example.com");
+        assert_eq!(encoded, "Nightly  Bcc: someone@example.com");
+
+        let report = ReportBuilder::new()
+            .with_title("Nightly\r\nBcc: someone@example.com\r\n".to_string())
+            .build::<EmailReport>();
+        let message = report.to_string_pretty();
+        let (headers, _) = message
+            .split_once("\r\n\r\n")
+            .expect("missing headers separator");
+        assert!(!headers.lines().any(|l| l.starts_with("Bcc:")));
+    }
+
+    #[test]
+    fn should_fold_long_ascii_headers() {
+        let subject =
+            "Nightly regression run of the checkout service: 3 failed, 1204 passed, 12 skipped";
+
+        let encoded = encode_header(subject);
+
+        assert!(encoded.contains("\r\n "));
+        assert!(encoded
+            .split("\r\n")
+            .all(|l| "Subject: ".len() + l.len() <= 78));
+        assert_eq!(encoded.replace("\r\n", ""), subject);
+    }
+}
@@ -0,0 +1,188 @@
+use crate::models::test_report::{PrettyPrint, ReportBuilder};
+use serde::Serialize;
+use url::Url;
+
+/// Struct that defines a Feishu/Lark custom bot interactive card message
+#[derive(Serialize)]
+pub struct FeishuReport {
+    pub msg_type: String,
+    pub card: Card,
+}
+
+/// Struct that defines an interactive card
+#[derive(Serialize)]
+pub struct Card {
+    pub header: CardHeader,
+    pub elements: Vec<CardElement>,
+}
+
+/// Struct that defines the header of an interactive card
+#[derive(Serialize)]
+pub struct CardHeader {
+    /// color theme of the header
+    pub template: String,
+    pub title: PlainText,
+}
+
+/// Enum that defines the variant of interactive card elements
+#[derive(Serialize)]
+#[serde(tag = "tag", rename_all = "lowercase")]
+pub enum CardElement {
+    Markdown { content: String },
+    Hr,
+    Action { actions: Vec<Button> },
+}
+
+/// Struct that defines a button opening the given url
+#[derive(Serialize)]
+#[serde(tag = "tag", rename = "button")]
+pub struct Button {
+    pub text: PlainText,
+    #[serde(rename = "type")]
+    pub button_type: String,
+    pub url: Url,
+}
+
+/// Struct that defines a plain text object of an interactive card
+#[derive(Serialize)]
+#[serde(tag = "tag", rename = "plain_text")]
+pub struct PlainText {
+    pub content: String,
+}
+
+/// Pretty print implementation for the Feishu/Lark report type
+impl PrettyPrint for FeishuReport {
+    fn to_string_pretty(&self) -> String {
+        serde_json::to_string_pretty(&self).expect("unable to serialize report to JSON")
+    }
+}
+
+/// Turns test results into a Feishu/Lark report
+impl From<ReportBuilder> for FeishuReport {
+    fn from(value: ReportBuilder) -> Self {
+        let template = if value.summary.failed > 0 {
+            "red"
+        } else if value.summary.total() == 0 {
+            "grey"
+        } else {
+            "green"
+        };
+
+        let mut elements = vec![
+            CardElement::Markdown {
+                content: format!(
+                    "**{}** failed, **{}** passed, **{}** skipped",
+                    value.summary.failed, value.summary.passed, value.summary.skipped
+                ),
+            },
+            CardElement::Hr,
+        ];
+
+        let content = if value.test_results.is_empty() {
+            "⚠️ unable to find test results".to_string()
+        } else {
+            value
+                .test_results
+                .iter()
+                .map(|t| t.to_commonmark_string())
+                .collect::<Vec<String>>()
+                .join("\n")
+        };
+        elements.push(CardElement::Markdown { content });
+
+        if let Some(link) = value.link {
+            elements.push(CardElement::Action {
+                actions: vec![Button {
+                    text: PlainText {
+                        content: "View details".to_string(),
+                    },
+                    button_type: "primary".to_string(),
+                    url: link,
+                }],
+            });
+        }
+
+        FeishuReport {
+            msg_type: "interactive".to_string(),
+            card: Card {
+                header: CardHeader {
+                    template: template.to_string(),
+                    title: PlainText {
+                        content: value.title,
+                    },
+                },
+                elements,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{
+        test_report::{PrettyPrint, ReportBuilder},
+        test_result::TestResult,
+        test_status::TestStatus,
+    };
+    use assert_json::assert_json;
+    use url::Url;
+
+    use super::FeishuReport;
+
+    #[test]
+    fn should_create_report_in_feishu_format() {
+        let test_failed = TestResult::builder()
+            .with_name("a test failed".to_string())
+            .with_status(TestStatus::Failed)
+            .with_failure_message("A failure".to_string())
+            .with_execution_time(1.2)
+            .build();
+
+        let report = ReportBuilder::new()
+            .with_title("A Feishu report".to_string())
+            .with_test_results(vec![test_failed.clone()])
+            .with_link(Url::parse("http://localhost/run/123").expect("unable to parse url"))
+            .build::<FeishuReport>();
+
+        assert_json!(report.to_string_pretty().as_str(), {
+                "msg_type": "interactive",
+                "card": {
+                    "header": {
+                        "template": "red",
+                        "title": {
+                            "tag": "plain_text",
+                            "content": "A Feishu report"
+                        }
+                    },
+                    "elements": [
+                        {
+                            "tag": "markdown",
+                            "content": "**1** failed, **0** passed, **0** skipped"
+                        },
+                        {
+                            "tag": "hr"
+                        },
+                        {
+                            "tag": "markdown",
+                            "content": test_failed.to_commonmark_string()
+                        },
+                        {
+                            "tag": "action",
+                            "actions": [
+                                {
+                                    "tag": "button",
+                                    "text": {
+                                        "tag": "plain_text",
+                                        "content": "View details"
+                                    },
+                                    "type": "primary",
+                                    "url": "http://localhost/run/123"
+                                }
+                            ]
+                        }
+                    ]
+                }
+            }
+        );
+    }
+}
@@ -0,0 +1,209 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::{
+    test_report::{PrettyPrint, ReportBuilder},
+    test_result::TestResult,
+    test_status::TestStatus,
+};
+
+/// Struct that defines a report made of GitHub Actions workflow commands, one `::error` command
+/// per failed test. Printed to the standard output of a workflow step, failures show up inline
+/// on the pull request diff when the test results provide file and line information.
+pub struct GithubWorkflowCommands {
+    pub test_results: Vec<TestResult>,
+}
+
+/// Turns test results into GitHub workflow commands
+impl From<ReportBuilder> for GithubWorkflowCommands {
+    fn from(value: ReportBuilder) -> Self {
+        GithubWorkflowCommands {
+            test_results: failed(value.test_results),
+        }
+    }
+}
+
+/// Pretty print implementation for the GitHub workflow commands report type
+impl PrettyPrint for GithubWorkflowCommands {
+    fn to_string_pretty(&self) -> String {
+        self.test_results
+            .iter()
+            .map(|t| {
+                let mut properties = vec![];
+                if let Some(file) = &t.file {
+                    properties.push(format!("file={}", escape_property(file)));
+                }
+                if let Some(line) = t.line {
+                    properties.push(format!("line={}", line));
+                }
+                properties.push(format!("title={}", escape_property(&t.qualified_name())));
+
+                format!(
+                    "::error {}::{}\n",
+                    properties.join(","),
+                    escape_data(&message(t))
+                )
+            })
+            .collect()
+    }
+}
+
+/// Struct that defines the `output.annotations` array of a GitHub Checks API check run, one
+/// annotation per failed test. The API accepts up to 50 annotations per request, so larger
+/// reports should be sent in chunks. Annotations need the path of a file of the repository, so
+/// tests that don't report their source file are left out, and belong to the check run summary.
+pub struct GithubAnnotations {
+    pub annotations: Vec<Annotation>,
+}
+
+/// A single check run annotation
+#[derive(Serialize, Deserialize)]
+pub struct Annotation {
+    pub path: String,
+    pub start_line: u32,
+    pub end_line: u32,
+    pub annotation_level: String,
+    pub title: String,
+    pub message: String,
+}
+
+/// Turns test results into GitHub check run annotations
+impl From<ReportBuilder> for GithubAnnotations {
+    fn from(value: ReportBuilder) -> Self {
+        let annotations = failed(value.test_results)
+            .iter()
+            .filter_map(|t| {
+                let path = t.file.clone()?;
+                let line = t.line.filter(|l| *l > 0).unwrap_or(1);
+                Some(Annotation {
+                    path,
+                    start_line: line,
+                    end_line: line,
+                    annotation_level: "failure".to_string(),
+                    title: t.qualified_name(),
+                    message: message(t),
+                })
+            })
+            .collect();
+
+        GithubAnnotations { annotations }
+    }
+}
+
+/// Pretty print implementation for the GitHub annotations report type
+impl PrettyPrint for GithubAnnotations {
+    fn to_string_pretty(&self) -> String {
+        serde_json::to_string_pretty(&self.annotations)
+            .expect("unable to serialize annotations to JSON")
+    }
+}
+
+/// Annotations only make sense for failures, whatever statuses the report builder includes
+fn failed(test_results: Vec<TestResult>) -> Vec<TestResult> {
+    test_results
+        .into_iter()
+        .filter(|t| t.status == TestStatus::Failed)
+        .collect()
+}
+
+fn message(test_result: &TestResult) -> String {
+    test_result
+        .failure_message
+        .clone()
+        .unwrap_or("⚠️ missing failure message".to_string())
+}
+
+/// Escapes the message of a workflow command
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escapes a property value of a workflow command
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{
+        test_report::{PrettyPrint, ReportBuilder},
+        test_result::TestResult,
+        test_status::TestStatus,
+    };
+    use assert_json::assert_json;
+    use indoc::indoc;
+
+    use super::{GithubAnnotations, GithubWorkflowCommands};
+
+    fn sample_builder() -> ReportBuilder {
+        ReportBuilder::new()
+            .include_passed()
+            .with_test_results(vec![
+                TestResult::builder()
+                    .with_name("a test passed".to_string())
+                    .with_status(TestStatus::Passed)
+                    .build(),
+                TestResult::builder()
+                    .with_name("a test failed".to_string())
+                    .with_suite_name("Suite::A".to_string())
+                    .with_status(TestStatus::Failed)
+                    .with_failure_message("expected 1, got 2\n100% wrong".to_string())
+                    .with_file("src/lib.rs".to_string())
+                    .with_line(42)
+                    .build(),
+                TestResult::builder()
+                    .with_name("another test failed".to_string())
+                    .with_status(TestStatus::Failed)
+                    .build(),
+            ])
+    }
+
+    #[test]
+    fn should_create_report_as_workflow_commands() {
+        let report = sample_builder().build::<GithubWorkflowCommands>();
+
+        assert_eq!(
+            report.to_string_pretty(),
+            indoc! {"
+                ::error file=src/lib.rs,line=42,title=Suite%3A%3AA › a test failed::expected 1, got 2%0A100%25 wrong
+                ::error title=another test failed::⚠️ missing failure message
+            "}
+        );
+    }
+
+    #[test]
+    fn should_create_report_as_check_run_annotations() {
+        let report = sample_builder().build::<GithubAnnotations>();
+
+        // the test without source file is left out
+        assert_eq!(report.annotations.len(), 1);
+        assert_json!(report.to_string_pretty().as_str(), [
+            {
+                "path": "src/lib.rs",
+                "start_line": 42,
+                "end_line": 42,
+                "annotation_level": "failure",
+                "title": "Suite::A › a test failed",
+                "message": "expected 1, got 2\n100% wrong"
+            }
+        ]);
+    }
+
+    #[test]
+    fn should_annotate_the_first_line_of_files_without_line() {
+        let report = ReportBuilder::new()
+            .with_test_results(vec![TestResult::builder()
+                .with_name("a test failed".to_string())
+                .with_status(TestStatus::Failed)
+                .with_file("tests/a_test.rs".to_string())
+                .build()])
+            .build::<GithubAnnotations>();
+
+        assert_eq!(report.annotations.len(), 1);
+        assert_eq!(report.annotations[0].path, "tests/a_test.rs");
+        assert_eq!(report.annotations[0].start_line, 1);
+        assert_eq!(report.annotations[0].end_line, 1);
+    }
+}
@@ -0,0 +1,298 @@
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::models::{
+    test_report::{PrettyPrint, ReportBuilder},
+    test_result::TestResult,
+    test_status::TestStatus,
+    test_summary::TestSummary,
+};
+
+use super::html::escape_html;
+
+/// Path used by Code Quality issues of tests that don't report their source file, as GitLab
+/// requires one
+const UNKNOWN_PATH: &str = ".";
+
+/// Struct that defines a GitLab Code Quality report, one issue per failed test. Uploaded as a
+/// `codequality` artifact, failures show up in the merge request widget and diff.
+pub struct CodeQualityReport {
+    pub issues: Vec<CodeQualityIssue>,
+}
+
+/// A single Code Quality issue, following the subset of the Code Climate format read by GitLab
+#[derive(Serialize, Deserialize)]
+pub struct CodeQualityIssue {
+    pub description: String,
+    pub check_name: String,
+    pub fingerprint: String,
+    pub severity: String,
+    pub location: Location,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Location {
+    pub path: String,
+    pub lines: Lines,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Lines {
+    pub begin: u32,
+}
+
+/// Turns test results into a GitLab Code Quality report
+impl From<ReportBuilder> for CodeQualityReport {
+    fn from(value: ReportBuilder) -> Self {
+        let issues = value
+            .test_results
+            .iter()
+            .filter(|t| t.status == TestStatus::Failed)
+            .map(|t| CodeQualityIssue {
+                description: format!(
+                    "{} failed: {}",
+                    t.qualified_name(),
+                    t.failure_message
+                        .clone()
+                        .unwrap_or("⚠️ missing failure message".to_string())
+                ),
+                check_name: "testvox/failed-test".to_string(),
+                fingerprint: fingerprint(t),
+                severity: "major".to_string(),
+                location: Location {
+                    path: t.file.clone().unwrap_or(UNKNOWN_PATH.to_string()),
+                    lines: Lines {
+                        begin: t.line.unwrap_or(1),
+                    },
+                },
+            })
+            .collect();
+
+        CodeQualityReport { issues }
+    }
+}
+
+/// Pretty print implementation for the GitLab Code Quality report type
+impl PrettyPrint for CodeQualityReport {
+    fn to_string_pretty(&self) -> String {
+        serde_json::to_string_pretty(&self.issues)
+            .expect("unable to serialize Code Quality report to JSON")
+    }
+}
+
+/// Struct that defines a GitLab merge request note, written in GitLab Flavored Markdown
+pub struct MergeRequestNote {
+    pub title: String,
+    pub summary: TestSummary,
+    pub test_results: Vec<TestResult>,
+    pub link: Option<Url>,
+}
+
+/// Turns test results into a GitLab merge request note
+impl From<ReportBuilder> for MergeRequestNote {
+    fn from(value: ReportBuilder) -> Self {
+        MergeRequestNote {
+            title: value.title,
+            summary: value.summary,
+            test_results: value.test_results,
+            link: value.link,
+        }
+    }
+}
+
+/// Pretty print implementation for the GitLab merge request note type
+impl PrettyPrint for MergeRequestNote {
+    fn to_string_pretty(&self) -> String {
+        let mut note = format!(
+            "## {}\n\n❌ **{}** failed · ✅ **{}** passed · ⏭️ **{}** skipped · ⏱️ {}s\n",
+            self.title,
+            self.summary.failed,
+            self.summary.passed,
+            self.summary.skipped,
+            self.summary.execution_time
+        );
+
+        if !self.test_results.is_empty() {
+            note.push_str("\n| | Suite | Test | Duration |\n|---|---|---|---|\n");
+            for t in self.test_results.iter() {
+                note.push_str(&format!(
+                    "| {} | {} | {} | {} |\n",
+                    status_emoji(&t.status),
+                    escape_table_cell(&t.suite_name.clone().unwrap_or_default()),
+                    escape_table_cell(&t.name),
+                    t.execution_time
+                        .map(|e| format!("{}s", e))
+                        .unwrap_or_default()
+                ));
+            }
+        }
+
+        for t in self
+            .test_results
+            .iter()
+            .filter(|t| t.status == TestStatus::Failed)
+        {
+            let failure_message = t
+                .failure_message
+                .clone()
+                .unwrap_or("⚠️ missing failure message".to_string());
+            let fence = code_fence(&failure_message);
+            note.push_str(&format!(
+                "\n<details>\n<summary>{}</summary>\n\n{fence}\n{}\n{fence}\n\n</details>\n",
+                escape_html(&t.qualified_name()),
+                failure_message,
+            ));
+        }
+
+        if let Some(link) = &self.link {
+            note.push_str(&format!("\n[View run]({})\n", link));
+        }
+
+        note
+    }
+}
+
+/// Yields a fingerprint that is stable across runs for the same test, so that GitLab can tell
+/// new failures from existing ones. Uses 64-bit FNV-1a, as the standard library hasher is not
+/// guaranteed to be stable across releases.
+fn fingerprint(test_result: &TestResult) -> String {
+    let key = [
+        test_result.suite_name.as_deref().unwrap_or_default(),
+        test_result.classname.as_deref().unwrap_or_default(),
+        test_result.name.as_str(),
+    ]
+    .join("\u{0}");
+
+    let hash = key.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+
+    format!("{:016x}", hash)
+}
+
+fn status_emoji(status: &TestStatus) -> &'static str {
+    match status {
+        TestStatus::Passed => "✅",
+        TestStatus::Failed => "❌",
+        TestStatus::Skipped => "⏭️",
+    }
+}
+
+fn escape_table_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', "<br>")
+}
+
+/// Yields a code fence longer than any backtick sequence in the given content
+fn code_fence(content: &str) -> String {
+    let longest = content
+        .split(|c| c != '`')
+        .map(|s| s.len())
+        .max()
+        .unwrap_or(0);
+    "`".repeat(longest.max(2) + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{
+        test_report::{PrettyPrint, ReportBuilder},
+        test_result::TestResult,
+        test_status::TestStatus,
+    };
+    use assert_json::assert_json;
+    use indoc::indoc;
+    use url::Url;
+
+    use super::{CodeQualityReport, MergeRequestNote};
+
+    fn sample_builder() -> ReportBuilder {
+        ReportBuilder::new()
+            .with_title("My repo".to_string())
+            .include_passed()
+            .with_test_results(vec![
+                TestResult::builder()
+                    .with_name("a test passed".to_string())
+                    .with_suite_name("Suite.A".to_string())
+                    .with_status(TestStatus::Passed)
+                    .with_execution_time(1.5)
+                    .build(),
+                TestResult::builder()
+                    .with_name("a test | failed".to_string())
+                    .with_suite_name("Suite.A".to_string())
+                    .with_status(TestStatus::Failed)
+                    .with_failure_message("expected ```1```".to_string())
+                    .with_file("src/lib.rs".to_string())
+                    .with_line(42)
+                    .with_execution_time(0.5)
+                    .build(),
+                TestResult::builder()
+                    .with_name("another test failed".to_string())
+                    .with_status(TestStatus::Failed)
+                    .build(),
+            ])
+    }
+
+    #[test]
+    fn should_create_code_quality_report() {
+        let report = sample_builder().build::<CodeQualityReport>();
+
+        assert_json!(report.to_string_pretty().as_str(), [
+            {
+                "description": "Suite.A › a test | failed failed: expected ```1```",
+                "check_name": "testvox/failed-test",
+                "fingerprint": "6e2c9d5db8fd1c54",
+                "severity": "major",
+                "location": { "path": "src/lib.rs", "lines": { "begin": 42 } }
+            },
+            {
+                "description": "another test failed failed: ⚠️ missing failure message",
+                "check_name": "testvox/failed-test",
+                "fingerprint": "beab8951cb34fda5",
+                "severity": "major",
+                "location": { "path": ".", "lines": { "begin": 1 } }
+            }
+        ]);
+    }
+
+    #[test]
+    fn should_create_merge_request_note() {
+        let report = sample_builder()
+            .with_link(Url::parse("http://localhost/run/123").expect("unable to parse url"))
+            .build::<MergeRequestNote>();
+
+        assert_eq!(
+            report.to_string_pretty(),
+            indoc! {r#"
+                ## My repo
+
+                ❌ **2** failed · ✅ **1** passed · ⏭️ **0** skipped · ⏱️ 2s
+
+                | | Suite | Test | Duration |
+                |---|---|---|---|
+                | ❌ | Suite.A | a test \| failed | 0.5s |
+                | ❌ |  | another test failed |  |
+                | ✅ | Suite.A | a test passed | 1.5s |
+
+                <details>
+                <summary>Suite.A › a test | failed</summary>
+
+                ````
+                expected ```1```
+                ````
+
+                </details>
+
+                <details>
+                <summary>another test failed</summary>
+
+                ```
+                ⚠️ missing failure message
+                ```
+
+                </details>
+
+                [View run](http://localhost/run/123)
+            "#}
+        );
+    }
+}
@@ -0,0 +1,256 @@
+use crate::models::{
+    test_report::{PrettyPrint, ReportBuilder},
+    test_result::{group_by_suite, TestResult},
+    test_status::TestStatus,
+    test_summary::TestSummary,
+};
+use url::Url;
+
+/// Inline stylesheet of the HTML report. Status filters are implemented with plain radio inputs,
+/// so that the page works without any script, even when opened from a CI artifact viewer.
+const STYLE: &str = r#"
+body { font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", Helvetica, Arial, sans-serif; margin: 0 auto; max-width: 960px; padding: 24px; color: #1f2328; }
+header { display: flex; align-items: baseline; justify-content: space-between; }
+header a { color: #0969da; }
+.summary { display: flex; gap: 12px; margin: 16px 0; }
+.summary div { border: 1px solid #d0d7de; border-radius: 6px; padding: 8px 16px; }
+.summary strong { display: block; font-size: 1.5em; }
+input[name=filter] { display: none; }
+nav label { border: 1px solid #d0d7de; border-radius: 16px; cursor: pointer; display: inline-block; margin-right: 4px; padding: 4px 12px; }
+#filter-all:checked ~ nav label[for=filter-all], #filter-failed:checked ~ nav label[for=filter-failed], #filter-skipped:checked ~ nav label[for=filter-skipped], #filter-passed:checked ~ nav label[for=filter-passed] { background: #1f2328; color: #ffffff; }
+#filter-failed:checked ~ main li.test:not(.failed), #filter-skipped:checked ~ main li.test:not(.skipped), #filter-passed:checked ~ main li.test:not(.passed) { display: none; }
+#filter-failed:checked ~ main details.suite:not(:has(li.failed)), #filter-skipped:checked ~ main details.suite:not(:has(li.skipped)), #filter-passed:checked ~ main details.suite:not(:has(li.passed)) { display: none; }
+details.suite { border: 1px solid #d0d7de; border-radius: 6px; margin: 12px 0; padding: 8px 16px; }
+details.suite > summary { cursor: pointer; font-weight: 600; }
+ul { list-style: none; padding: 0; }
+li.test { border-top: 1px solid #eaeef2; padding: 6px 0; }
+.failed .status { color: #cf222e; }
+.skipped .status { color: #9a6700; }
+.passed .status { color: #1a7f37; }
+.time { color: #656d76; font-family: monospace; }
+details.failure pre { background: #f6f8fa; border-radius: 6px; overflow-x: auto; padding: 8px; white-space: pre-wrap; }
+"#;
+
+/// Struct that defines a self-contained HTML report
+pub struct HtmlReport {
+    /// the title of the report
+    pub title: String,
+    /// counters over all the parsed test results
+    pub summary: TestSummary,
+    /// reported test results, grouped by suite name in order of appearance
+    pub suites: Vec<(String, Vec<TestResult>)>,
+    /// optional link to view more details related to the report
+    pub link: Option<Url>,
+}
+
+/// Turns test results into an HTML report
+impl From<ReportBuilder> for HtmlReport {
+    fn from(value: ReportBuilder) -> Self {
+        let suites = group_by_suite(value.test_results);
+
+        HtmlReport {
+            title: value.title,
+            summary: value.summary,
+            suites,
+            link: value.link,
+        }
+    }
+}
+
+/// Pretty print implementation for the HTML report type
+impl PrettyPrint for HtmlReport {
+    fn to_string_pretty(&self) -> String {
+        let title = escape_html(&self.title);
+        let link = self
+            .link
+            .as_ref()
+            .map(|l| format!("<a href=\"{}\">View details</a>", escape_html(l.as_str())))
+            .unwrap_or_default();
+
+        let suites: String = if self.suites.is_empty() {
+            "<p>⚠️ unable to find test results</p>\n".to_string()
+        } else {
+            self.suites
+                .iter()
+                .map(|(name, tests)| suite_to_html(name, tests))
+                .collect()
+        };
+
+        format!(
+            "<!DOCTYPE html>\n\
+             <html lang=\"en\">\n\
+             <head>\n\
+             <meta charset=\"utf-8\">\n\
+             <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+             <title>{title}</title>\n\
+             <style>{STYLE}</style>\n\
+             </head>\n\
+             <body>\n\
+             <header><h1>{title}</h1>{link}</header>\n\
+             <section class=\"summary\">\
+             <div class=\"failed\"><strong class=\"status\">{failed}</strong>failed</div>\
+             <div class=\"skipped\"><strong class=\"status\">{skipped}</strong>skipped</div>\
+             <div class=\"passed\"><strong class=\"status\">{passed}</strong>passed</div>\
+             <div><strong class=\"time\">{time}s</strong>total time</div>\
+             </section>\n\
+             <input type=\"radio\" name=\"filter\" id=\"filter-all\" checked>\n\
+             <input type=\"radio\" name=\"filter\" id=\"filter-failed\">\n\
+             <input type=\"radio\" name=\"filter\" id=\"filter-skipped\">\n\
+             <input type=\"radio\" name=\"filter\" id=\"filter-passed\">\n\
+             <nav>\
+             <label for=\"filter-all\">All</label>\
+             <label for=\"filter-failed\">Failed</label>\
+             <label for=\"filter-skipped\">Skipped</label>\
+             <label for=\"filter-passed\">Passed</label>\
+             </nav>\n\
+             <main>\n{suites}</main>\n\
+             </body>\n\
+             </html>\n",
+            failed = self.summary.failed,
+            skipped = self.summary.skipped,
+            passed = self.summary.passed,
+            time = self.summary.execution_time,
+        )
+    }
+}
+
+fn suite_to_html(name: &str, tests: &[TestResult]) -> String {
+    let failed = tests
+        .iter()
+        .filter(|t| t.status == TestStatus::Failed)
+        .count();
+    let tests: String = tests.iter().map(test_to_html).collect();
+
+    // suites with failures are expanded by default, so that failures are visible at first glance
+    format!(
+        "<details class=\"suite\"{open}>\n<summary>{name}</summary>\n<ul>\n{tests}</ul>\n</details>\n",
+        open = if failed > 0 { " open" } else { "" },
+        name = escape_html(name),
+    )
+}
+
+fn test_to_html(test_result: &TestResult) -> String {
+    let status = test_result.status.to_string().to_lowercase();
+    let name = escape_html(&test_result.name);
+    let time = test_result
+        .execution_time
+        .map(|t| format!(" <span class=\"time\">{}s</span>", t))
+        .unwrap_or_default();
+
+    let failure = match test_result.status {
+        TestStatus::Failed => {
+            let message = test_result
+                .failure_message
+                .clone()
+                .unwrap_or("⚠️ missing failure message".to_string());
+            format!(
+                "\n<details class=\"failure\"><summary>{}</summary><pre>{}</pre></details>",
+                escape_html(message.lines().next().unwrap_or_default()),
+                escape_html(&message)
+            )
+        }
+        _ => String::new(),
+    };
+
+    format!(
+        "<li class=\"test {status}\"><span class=\"status\">{status}</span> {name}{time}{failure}</li>\n"
+    )
+}
+
+/// Escapes the characters that have a special meaning in HTML and XML documents
+pub(crate) fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{
+        test_report::{PrettyPrint, ReportBuilder},
+        test_result::TestResult,
+        test_status::TestStatus,
+    };
+    use url::Url;
+
+    use super::{escape_html, HtmlReport};
+
+    #[test]
+    fn should_create_report_in_html_format_empty() {
+        let report = ReportBuilder::new()
+            .with_title("An empty HTML report".to_string())
+            .build::<HtmlReport>();
+
+        let html = report.to_string_pretty();
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>An empty HTML report</title>"));
+        assert!(html.contains("<p>⚠️ unable to find test results</p>"));
+        assert!(!html.contains("View details"));
+    }
+
+    #[test]
+    fn should_create_report_in_html_format() {
+        let report = ReportBuilder::new()
+            .with_title("An <HTML> report".to_string())
+            .include_skipped()
+            .with_test_results(vec![
+                TestResult::builder()
+                    .with_name("a test passed".to_string())
+                    .with_suite_name("Suite.A".to_string())
+                    .with_status(TestStatus::Passed)
+                    .with_execution_time(3.3)
+                    .build(),
+                TestResult::builder()
+                    .with_name("a test skipped".to_string())
+                    .with_suite_name("Suite.A".to_string())
+                    .with_status(TestStatus::Skipped)
+                    .build(),
+                TestResult::builder()
+                    .with_name("a test failed".to_string())
+                    .with_suite_name("Suite.B".to_string())
+                    .with_status(TestStatus::Failed)
+                    .with_failure_message("expected <1>\nbut was <2>".to_string())
+                    .with_execution_time(1.2)
+                    .build(),
+            ])
+            .with_link(Url::parse("http://localhost/run/123").expect("unable to parse url"))
+            .build::<HtmlReport>();
+
+        assert_eq!(report.summary.total(), 3);
+        assert_eq!(report.suites.len(), 2);
+        assert_eq!(report.suites[0].0, "Suite.B");
+        assert_eq!(report.suites[1].1.len(), 1);
+
+        let html = report.to_string_pretty();
+
+        assert!(html.contains("<h1>An &lt;HTML&gt; report</h1>"));
+        assert!(html.contains("<a href=\"http://localhost/run/123\">View details</a>"));
+        assert!(html.contains("<strong class=\"status\">1</strong>passed"));
+        assert!(html.contains("<details class=\"suite\" open>\n<summary>Suite.B</summary>"));
+        assert!(html.contains("<details class=\"suite\">\n<summary>Suite.A</summary>"));
+        assert!(html.contains(
+            "<details class=\"failure\"><summary>expected &lt;1&gt;</summary><pre>expected &lt;1&gt;\nbut was &lt;2&gt;</pre></details>"
+        ));
+        assert!(html.contains("<li class=\"test skipped\">"));
+        assert!(!html.contains("a test passed"));
+        assert!(!html.contains("<script"));
+    }
+
+    #[test]
+    fn should_escape_html_special_characters() {
+        assert_eq!(
+            escape_html("<a href=\"x\">Tom & Jerry's</a>"),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&#39;s&lt;/a&gt;"
+        );
+    }
+}
@@ -0,0 +1,433 @@
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::models::{
+    test_report::{PrettyPrint, ReportBuilder},
+    test_result::TestResult,
+    test_status::TestStatus,
+};
+
+/// Maximum length of the summary of a Jira issue
+const MAX_SUMMARY_LENGTH: usize = 255;
+
+/// Struct that defines a report made of Jira issue creation payloads, one per failed test. The
+/// pretty printed report is the body of a `POST /rest/api/3/issue/bulk` request.
+pub struct JiraReport {
+    /// the title of the report, used as prefix of the issue summaries
+    pub title: String,
+    /// the failed tests
+    pub test_results: Vec<TestResult>,
+    /// an optional link to the test run, added to the issue descriptions
+    pub link: Option<Url>,
+    /// key of the project in which issues are created
+    pub project_key: String,
+    /// name of the type of the created issues
+    pub issue_type: String,
+    /// labels added to the created issues
+    pub labels: Vec<String>,
+}
+
+impl JiraReport {
+    /// Sets the key of the project in which issues are created. Jira rejects payloads without one.
+    pub fn with_project_key(mut self, project_key: String) -> JiraReport {
+        self.project_key = project_key;
+        self
+    }
+
+    /// Overrides the default "Bug" issue type
+    pub fn with_issue_type(mut self, issue_type: String) -> JiraReport {
+        self.issue_type = issue_type;
+        self
+    }
+
+    /// Adds a label to the created issues, besides the default "testvox" one. Whitespaces are
+    /// replaced by dashes, as Jira labels can't contain them.
+    pub fn with_label(mut self, label: String) -> JiraReport {
+        self.labels
+            .push(label.split_whitespace().collect::<Vec<&str>>().join("-"));
+        self
+    }
+
+    /// Yields the payloads of the issues to create
+    pub fn issue_updates(&self) -> BulkCreateIssues {
+        let issue_updates = self
+            .test_results
+            .iter()
+            .map(|t| IssueUpdate {
+                fields: IssueFields {
+                    project: Project {
+                        key: self.project_key.clone(),
+                    },
+                    issuetype: IssueType {
+                        name: self.issue_type.clone(),
+                    },
+                    summary: summary(&self.title, t),
+                    description: description(t, self.link.as_ref()),
+                    labels: self.labels.clone(),
+                },
+            })
+            .collect();
+
+        BulkCreateIssues { issue_updates }
+    }
+}
+
+/// Body of a bulk issue creation request
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkCreateIssues {
+    pub issue_updates: Vec<IssueUpdate>,
+}
+
+/// Payload of a single issue creation request
+#[derive(Serialize, Deserialize)]
+pub struct IssueUpdate {
+    pub fields: IssueFields,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct IssueFields {
+    pub project: Project,
+    pub issuetype: IssueType,
+    pub summary: String,
+    pub description: AdfNode,
+    pub labels: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Project {
+    pub key: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct IssueType {
+    pub name: String,
+}
+
+/// A node of a document in Atlassian Document Format
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum AdfNode {
+    Doc {
+        version: u8,
+        content: Vec<AdfNode>,
+    },
+    Paragraph {
+        content: Vec<AdfNode>,
+    },
+    Heading {
+        attrs: HeadingAttributes,
+        content: Vec<AdfNode>,
+    },
+    CodeBlock {
+        content: Vec<AdfNode>,
+    },
+    Text {
+        text: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        marks: Vec<AdfMark>,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct HeadingAttributes {
+    pub level: u8,
+}
+
+/// A text mark in Atlassian Document Format
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum AdfMark {
+    Code,
+    Link { attrs: LinkAttributes },
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct LinkAttributes {
+    pub href: String,
+}
+
+/// Turns test results into a Jira report
+impl From<ReportBuilder> for JiraReport {
+    fn from(value: ReportBuilder) -> Self {
+        JiraReport {
+            title: value.title,
+            test_results: value
+                .test_results
+                .into_iter()
+                .filter(|t| t.status == TestStatus::Failed)
+                .collect(),
+            link: value.link,
+            project_key: String::new(),
+            issue_type: "Bug".to_string(),
+            labels: vec!["testvox".to_string()],
+        }
+    }
+}
+
+/// Pretty print implementation for the Jira report type
+impl PrettyPrint for JiraReport {
+    fn to_string_pretty(&self) -> String {
+        serde_json::to_string_pretty(&self.issue_updates())
+            .expect("unable to serialize Jira issues to JSON")
+    }
+}
+
+fn summary(title: &str, test_result: &TestResult) -> String {
+    let name = test_result.qualified_name();
+    let summary = match title.is_empty() {
+        true => format!("{} failed", name),
+        false => format!("{}: {} failed", title, name),
+    };
+    let summary = summary.split_whitespace().collect::<Vec<&str>>().join(" ");
+
+    if summary.chars().count() <= MAX_SUMMARY_LENGTH {
+        summary
+    } else {
+        let mut truncated: String = summary.chars().take(MAX_SUMMARY_LENGTH - 1).collect();
+        truncated.push('…');
+        truncated
+    }
+}
+
+fn description(test_result: &TestResult, link: Option<&Url>) -> AdfNode {
+    let mut details = vec![text("Test "), code(&test_result.name)];
+    if let Some(suite_name) = &test_result.suite_name {
+        details.extend([text(" of suite "), code(suite_name)]);
+    }
+    details.push(text(" failed."));
+    if let Some(file) = &test_result.file {
+        details.extend([text(" Defined in "), code(file)]);
+        if let Some(line) = test_result.line {
+            details.push(text(&format!(" at line {}", line)));
+        }
+        details.push(text("."));
+    }
+
+    let mut content = vec![AdfNode::Paragraph {
+        content: non_empty(details),
+    }];
+
+    content.extend(section(
+        "Failure message",
+        test_result
+            .failure_message
+            .as_deref()
+            .unwrap_or("⚠️ missing failure message"),
+    ));
+    if let Some(stack_trace) = &test_result.stack_trace {
+        content.extend(section("Stack trace", stack_trace));
+    }
+
+    if let Some(link) = link {
+        content.push(AdfNode::Paragraph {
+            content: vec![AdfNode::Text {
+                text: "View test run".to_string(),
+                marks: vec![AdfMark::Link {
+                    attrs: LinkAttributes {
+                        href: link.to_string(),
+                    },
+                }],
+            }],
+        });
+    }
+
+    AdfNode::Doc {
+        version: 1,
+        content,
+    }
+}
+
+fn section(heading: &str, body: &str) -> [AdfNode; 2] {
+    [
+        AdfNode::Heading {
+            attrs: HeadingAttributes { level: 3 },
+            content: vec![text(heading)],
+        },
+        AdfNode::CodeBlock {
+            content: non_empty(vec![text(body)]),
+        },
+    ]
+}
+
+/// Drops the empty text nodes, that aren't valid in Atlassian Document Format
+fn non_empty(nodes: Vec<AdfNode>) -> Vec<AdfNode> {
+    nodes
+        .into_iter()
+        .filter(|n| !matches!(n, AdfNode::Text { text, .. } if text.is_empty()))
+        .collect()
+}
+
+fn text(text: &str) -> AdfNode {
+    AdfNode::Text {
+        text: text.to_string(),
+        marks: vec![],
+    }
+}
+
+fn code(text: &str) -> AdfNode {
+    AdfNode::Text {
+        text: text.to_string(),
+        marks: vec![AdfMark::Code],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{
+        test_report::{PrettyPrint, ReportBuilder},
+        test_result::TestResult,
+        test_status::TestStatus,
+    };
+    use assert_json::assert_json;
+    use url::Url;
+
+    use super::{JiraReport, MAX_SUMMARY_LENGTH};
+
+    #[test]
+    fn should_create_jira_issue_payloads_for_failed_tests() {
+        let report = ReportBuilder::new()
+            .with_title("Nightly".to_string())
+            .include_passed()
+            .with_test_results(vec![
+                TestResult::builder()
+                    .with_name("a test passed".to_string())
+                    .with_status(TestStatus::Passed)
+                    .build(),
+                TestResult::builder()
+                    .with_name("a test failed".to_string())
+                    .with_suite_name("Suite.A".to_string())
+                    .with_status(TestStatus::Failed)
+                    .with_failure_message("expected 1".to_string())
+                    .with_stack_trace("at a_test.rs:3".to_string())
+                    .with_file("tests/a_test.rs".to_string())
+                    .with_line(3)
+                    .build(),
+            ])
+            .with_link(Url::parse("http://localhost/run/123").expect("unable to parse url"))
+            .build::<JiraReport>()
+            .with_project_key("QA".to_string())
+            .with_label("nightly run".to_string());
+
+        assert_json!(report.to_string_pretty().as_str(), {
+            "issueUpdates": [
+                {
+                    "fields": {
+                        "project": { "key": "QA" },
+                        "issuetype": { "name": "Bug" },
+                        "summary": "Nightly: Suite.A › a test failed failed",
+                        "labels": ["testvox", "nightly-run"],
+                        "description": {
+                            "type": "doc",
+                            "version": 1,
+                            "content": [
+                                {
+                                    "type": "paragraph",
+                                    "content": [
+                                        { "type": "text", "text": "Test " },
+                                        { "type": "text", "text": "a test failed", "marks": [{ "type": "code" }] },
+                                        { "type": "text", "text": " of suite " },
+                                        { "type": "text", "text": "Suite.A", "marks": [{ "type": "code" }] },
+                                        { "type": "text", "text": " failed." },
+                                        { "type": "text", "text": " Defined in " },
+                                        { "type": "text", "text": "tests/a_test.rs", "marks": [{ "type": "code" }] },
+                                        { "type": "text", "text": " at line 3" },
+                                        { "type": "text", "text": "." }
+                                    ]
+                                },
+                                {
+                                    "type": "heading",
+                                    "attrs": { "level": 3 },
+                                    "content": [{ "type": "text", "text": "Failure message" }]
+                                },
+                                {
+                                    "type": "codeBlock",
+                                    "content": [{ "type": "text", "text": "expected 1" }]
+                                },
+                                {
+                                    "type": "heading",
+                                    "attrs": { "level": 3 },
+                                    "content": [{ "type": "text", "text": "Stack trace" }]
+                                },
+                                {
+                                    "type": "codeBlock",
+                                    "content": [{ "type": "text", "text": "at a_test.rs:3" }]
+                                },
+                                {
+                                    "type": "paragraph",
+                                    "content": [
+                                        {
+                                            "type": "text",
+                                            "text": "View test run",
+                                            "marks": [{ "type": "link", "attrs": { "href": "http://localhost/run/123" } }]
+                                        }
+                                    ]
+                                }
+                            ]
+                        }
+                    }
+                }
+            ]
+        });
+    }
+
+    #[test]
+    fn should_truncate_long_summaries() {
+        let report = ReportBuilder::new()
+            .with_test_results(vec![TestResult::builder()
+                .with_name(format!("a\nvery {} long test", "x".repeat(300)))
+                .with_status(TestStatus::Failed)
+                .build()])
+            .build::<JiraReport>();
+
+        let summary = &report.issue_updates().issue_updates[0].fields.summary;
+
+        assert_eq!(summary.chars().count(), MAX_SUMMARY_LENGTH);
+        assert!(summary.starts_with("a very xxx"));
+        assert!(summary.ends_with('…'));
+    }
+
+    #[test]
+    fn should_skip_empty_text_nodes() {
+        let report = ReportBuilder::new()
+            .with_test_results(vec![TestResult::builder()
+                .with_name("a test failed".to_string())
+                .with_suite_name("".to_string())
+                .with_status(TestStatus::Failed)
+                .with_failure_message("".to_string())
+                .build()])
+            .build::<JiraReport>()
+            .with_project_key("QA".to_string());
+
+        assert_json!(report.to_string_pretty().as_str(), {
+            "issueUpdates": [
+                {
+                    "fields": {
+                        "description": {
+                            "content": [
+                                {
+                                    "type": "paragraph",
+                                    "content": [
+                                        { "type": "text", "text": "Test " },
+                                        { "type": "text", "text": "a test failed", "marks": [{ "type": "code" }] },
+                                        { "type": "text", "text": " of suite " },
+                                        { "type": "text", "text": " failed." }
+                                    ]
+                                },
+                                {
+                                    "type": "heading",
+                                    "attrs": { "level": 3 },
+                                    "content": [{ "type": "text", "text": "Failure message" }]
+                                },
+                                {
+                                    "type": "codeBlock",
+                                    "content": []
+                                }
+                            ]
+                        }
+                    }
+                }
+            ]
+        });
+    }
+}
@@ -0,0 +1,161 @@
+use crate::models::test_report::{PrettyPrint, Report, ReportBuilder};
+use serde::{Deserialize, Serialize};
+
+/// Version of the JSON schema the report complies with
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Published location of the JSON schema the report complies with
+pub const SCHEMA_URL: &str =
+    "https://raw.githubusercontent.com/dili91/testvox/main/schemas/report.v1.json";
+
+/// The JSON schema document describing the report
+pub const SCHEMA: &str = include_str!("../../schemas/report.v1.json");
+
+/// Struct that defines a canonical JSON report, following a stable and versioned schema
+#[derive(Serialize, Deserialize)]
+pub struct JsonReport {
+    #[serde(rename = "$schema")]
+    pub schema: String,
+    pub version: u32,
+    #[serde(flatten)]
+    pub report: Report,
+}
+
+/// Pretty print implementation for the JSON report type
+impl PrettyPrint for JsonReport {
+    fn to_string_pretty(&self) -> String {
+        serde_json::to_string_pretty(&self).expect("unable to serialize report to JSON")
+    }
+}
+
+/// Turns test results into a JSON report
+impl From<ReportBuilder> for JsonReport {
+    fn from(value: ReportBuilder) -> Self {
+        JsonReport {
+            schema: SCHEMA_URL.to_string(),
+            version: SCHEMA_VERSION,
+            report: Report::from(value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{
+        test_report::{PrettyPrint, Report, ReportBuilder},
+        test_result::TestResult,
+        test_status::TestStatus,
+    };
+    use assert_json::assert_json;
+    use url::Url;
+
+    use super::{JsonReport, SCHEMA, SCHEMA_URL};
+
+    fn sample_report() -> JsonReport {
+        ReportBuilder::new()
+            .with_title("A JSON report".to_string())
+            .include_passed()
+            .with_test_results(vec![
+                TestResult::builder()
+                    .with_name("a test passed".to_string())
+                    .with_suite_name("Suite.A".to_string())
+                    .with_classname("a.Test".to_string())
+                    .with_status(TestStatus::Passed)
+                    .with_execution_time(1.5)
+                    .build(),
+                TestResult::builder()
+                    .with_name("a test skipped".to_string())
+                    .with_status(TestStatus::Skipped)
+                    .build(),
+                TestResult::builder()
+                    .with_name("a test failed".to_string())
+                    .with_status(TestStatus::Failed)
+                    .with_failure_message("A failure".to_string())
+                    .with_file("tests/a_test.rs".to_string())
+                    .with_line(12)
+                    .with_execution_time(0.5)
+                    .build(),
+            ])
+            .with_link(Url::parse("http://localhost/run/123").expect("unable to parse url"))
+            .build::<JsonReport>()
+    }
+
+    #[test]
+    fn should_create_report_in_json_format() {
+        assert_json!(sample_report().to_string_pretty().as_str(), {
+                "$schema": SCHEMA_URL,
+                "version": 1,
+                "title": "A JSON report",
+                "link": "http://localhost/run/123",
+                "summary": {
+                    "passed": 1,
+                    "failed": 1,
+                    "skipped": 1,
+                    "execution_time": 2.0,
+                    "flaky": 0
+                },
+                "test_results": [
+                    {
+                        "name": "a test failed",
+                        "suite_name": null,
+                        "classname": null,
+                        "file": "tests/a_test.rs",
+                        "line": 12,
+                        "execution_time": 0.5,
+                        "status": "failed",
+                        "failure_message": "A failure",
+                        "stack_trace": null,
+                        "flaky": false
+                    },
+                    {
+                        "name": "a test passed",
+                        "suite_name": "Suite.A",
+                        "classname": "a.Test",
+                        "file": null,
+                        "line": null,
+                        "execution_time": 1.5,
+                        "status": "passed",
+                        "failure_message": null,
+                        "stack_trace": null,
+                        "flaky": false
+                    }
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn should_deserialize_a_json_report() {
+        let report = sample_report();
+
+        let deserialized: JsonReport =
+            serde_json::from_str(&report.to_string_pretty()).expect("unable to deserialize report");
+
+        assert_eq!(deserialized.version, report.version);
+        // per-suite counters are not part of the schema
+        assert_eq!(
+            deserialized.report,
+            Report {
+                suite_summaries: vec![],
+                ..report.report
+            }
+        );
+    }
+
+    #[test]
+    fn should_create_report_complying_with_the_published_schema() {
+        let schema: serde_json::Value =
+            serde_json::from_str(SCHEMA).expect("unable to parse schema");
+        let report: serde_json::Value = serde_json::from_str(&sample_report().to_string_pretty())
+            .expect("unable to parse report");
+
+        let validator = jsonschema::validator_for(&schema).expect("invalid schema");
+        let errors: Vec<String> = validator
+            .iter_errors(&report)
+            .map(|e| e.to_string())
+            .collect();
+
+        assert!(errors.is_empty(), "{:?}", errors);
+        assert_eq!(schema["$id"], SCHEMA_URL);
+    }
+}
@@ -0,0 +1,263 @@
+use crate::models::{
+    test_report::{PrettyPrint, ReportBuilder},
+    test_result::{group_by_suite, TestResult},
+    test_status::TestStatus,
+    test_summary::TestSummary,
+};
+
+use super::html::escape_html;
+
+/// Struct that defines a JUnit XML report, with one `testsuite` per suite name. Only the test
+/// statuses included in the report builder are written, so passed and skipped tests should be
+/// included to produce a complete document.
+pub struct JunitReport {
+    /// the title of the report, used as name of the `testsuites` root element
+    pub title: String,
+    /// test results, grouped by suite name in order of appearance
+    pub suites: Vec<(String, Vec<TestResult>)>,
+}
+
+/// Turns test results into a JUnit XML report
+impl From<ReportBuilder> for JunitReport {
+    fn from(value: ReportBuilder) -> Self {
+        // tests without a suite are grouped under a suite named after the report
+        let test_results = value
+            .test_results
+            .into_iter()
+            .map(|mut t| {
+                t.suite_name = t.suite_name.or(Some(value.title.clone()));
+                t
+            })
+            .collect();
+        let suites = group_by_suite(test_results);
+
+        JunitReport {
+            title: value.title,
+            suites,
+        }
+    }
+}
+
+/// Pretty print implementation for the JUnit report type
+impl PrettyPrint for JunitReport {
+    fn to_string_pretty(&self) -> String {
+        let all_tests: Vec<TestResult> = self
+            .suites
+            .iter()
+            .flat_map(|(_, tests)| tests.clone())
+            .collect();
+        let summary = TestSummary::from(all_tests.as_slice());
+
+        let mut xml = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites name=\"{}\" {}>\n",
+            escape_xml_attribute(&self.title),
+            aggregates(&summary)
+        );
+
+        for (name, tests) in self.suites.iter() {
+            xml.push_str(&format!(
+                "  <testsuite name=\"{}\" {}>\n",
+                escape_xml_attribute(name),
+                aggregates(&TestSummary::from(tests.as_slice()))
+            ));
+            tests.iter().for_each(|t| xml.push_str(&test_to_xml(t)));
+            xml.push_str("  </testsuite>\n");
+        }
+
+        xml.push_str("</testsuites>\n");
+        xml
+    }
+}
+
+fn aggregates(summary: &TestSummary) -> String {
+    format!(
+        "tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"{}\" time=\"{}\"",
+        summary.total(),
+        summary.failed,
+        summary.skipped,
+        summary.execution_time
+    )
+}
+
+fn test_to_xml(test_result: &TestResult) -> String {
+    let mut attributes = format!("name=\"{}\"", escape_xml_attribute(&test_result.name));
+    if let Some(classname) = &test_result.classname {
+        attributes.push_str(&format!(
+            " classname=\"{}\"",
+            escape_xml_attribute(classname)
+        ));
+    }
+    if let Some(file) = &test_result.file {
+        attributes.push_str(&format!(" file=\"{}\"", escape_xml_attribute(file)));
+    }
+    if let Some(line) = test_result.line {
+        attributes.push_str(&format!(" line=\"{}\"", line));
+    }
+    if let Some(time) = test_result.execution_time {
+        attributes.push_str(&format!(" time=\"{}\"", time));
+    }
+
+    match test_result.status {
+        TestStatus::Passed => format!("    <testcase {attributes} />\n"),
+        TestStatus::Skipped => {
+            format!("    <testcase {attributes}>\n      <skipped />\n    </testcase>\n")
+        }
+        TestStatus::Failed => {
+            let message = match &test_result.failure_message {
+                Some(message) => format!(" message=\"{}\"", escape_xml_attribute(message)),
+                None => String::new(),
+            };
+            let failure = match &test_result.stack_trace {
+                Some(stack_trace) => {
+                    format!("<failure{message}>{}</failure>", escape_xml(stack_trace))
+                }
+                None => format!("<failure{message} />"),
+            };
+            format!("    <testcase {attributes}>\n      {failure}\n    </testcase>\n")
+        }
+    }
+}
+
+/// Escapes a value for XML, dropping the control characters that XML 1.0 does not allow, such as
+/// the ANSI color codes that some test frameworks include in failure messages
+fn escape_xml(value: &str) -> String {
+    let valid: String = value
+        .chars()
+        .filter(|c| matches!(c, '\t' | '\n' | '\r') || !c.is_control())
+        .collect();
+    escape_html(&valid)
+}
+
+/// Escapes a value for XML attributes, where parsers would otherwise normalize line breaks and tabs
+/// into spaces
+fn escape_xml_attribute(value: &str) -> String {
+    escape_xml(value)
+        .replace('\n', "&#10;")
+        .replace('\r', "&#13;")
+        .replace('\t', "&#9;")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::{
+        models::{
+            test_report::{PrettyPrint, ReportBuilder},
+            test_result::TestResult,
+            test_status::TestStatus,
+        },
+        parsers::{junit::JunitTestParser, TestParser},
+    };
+    use indoc::indoc;
+
+    use super::JunitReport;
+
+    #[test]
+    fn should_create_report_in_junit_format() {
+        let report = ReportBuilder::new()
+            .with_title("Merged <results>".to_string())
+            .include_passed()
+            .include_skipped()
+            .with_test_results(vec![
+                TestResult::builder()
+                    .with_name("a test passed".to_string())
+                    .with_suite_name("Suite.A".to_string())
+                    .with_classname("a.Test".to_string())
+                    .with_status(TestStatus::Passed)
+                    .with_execution_time(1.5)
+                    .build(),
+                TestResult::builder()
+                    .with_name("a test skipped".to_string())
+                    .with_suite_name("Suite.A".to_string())
+                    .with_status(TestStatus::Skipped)
+                    .build(),
+                TestResult::builder()
+                    .with_name("a test failed".to_string())
+                    .with_suite_name("Suite.B".to_string())
+                    .with_status(TestStatus::Failed)
+                    .with_failure_message("expected \"1\"\u{1b}[31m".to_string())
+                    .with_stack_trace("at a.Test(a_test.rs:3) <main>".to_string())
+                    .with_execution_time(0.5)
+                    .build(),
+            ])
+            .build::<JunitReport>();
+
+        assert_eq!(
+            report.to_string_pretty(),
+            indoc! {r#"
+                <?xml version="1.0" encoding="UTF-8"?>
+                <testsuites name="Merged &lt;results&gt;" tests="3" failures="1" errors="0" skipped="1" time="2">
+                  <testsuite name="Suite.B" tests="1" failures="1" errors="0" skipped="0" time="0.5">
+                    <testcase name="a test failed" time="0.5">
+                      <failure message="expected &quot;1&quot;[31m">at a.Test(a_test.rs:3) &lt;main&gt;</failure>
+                    </testcase>
+                  </testsuite>
+                  <testsuite name="Suite.A" tests="2" failures="0" errors="0" skipped="1" time="1.5">
+                    <testcase name="a test skipped">
+                      <skipped />
+                    </testcase>
+                    <testcase name="a test passed" classname="a.Test" time="1.5" />
+                  </testsuite>
+                </testsuites>
+            "#}
+        );
+    }
+
+    #[test]
+    fn should_write_junit_report_that_parses_back_into_the_same_results() {
+        let original = JunitTestParser::from(
+            fs::read_to_string("./test-data/junit.xml").expect("Unable to read file"),
+        )
+        .parse()
+        .expect("Unable to parse test results content");
+
+        let report = ReportBuilder::new()
+            .include_passed()
+            .include_skipped()
+            .with_test_results(original.clone())
+            .build::<JunitReport>();
+
+        let parsed = JunitTestParser::from(report.to_string_pretty())
+            .parse()
+            .expect("Unable to parse written report");
+
+        assert_eq!(parsed.len(), original.len());
+        for t in original {
+            let p = parsed
+                .iter()
+                .find(|p| p.name == t.name)
+                .expect("Failed to find expected test");
+            assert_eq!(p.suite_name, t.suite_name);
+            assert_eq!(p.classname, t.classname);
+            assert_eq!(p.file, t.file);
+            assert_eq!(p.line, t.line);
+            assert_eq!(p.status, t.status);
+            assert_eq!(p.execution_time, t.execution_time);
+            assert_eq!(p.failure_message, t.failure_message);
+            assert_eq!(p.stack_trace, t.stack_trace);
+        }
+    }
+
+    #[test]
+    fn should_keep_line_breaks_of_failure_messages() {
+        let report = ReportBuilder::new()
+            .with_test_results(vec![TestResult::builder()
+                .with_name("a test failed".to_string())
+                .with_status(TestStatus::Failed)
+                .with_failure_message("expected:\r\n\t1\nbut was:\n\t2".to_string())
+                .build()])
+            .build::<JunitReport>();
+
+        let xml = report.to_string_pretty();
+        assert!(xml.contains("message=\"expected:&#13;&#10;&#9;1&#10;but was:&#10;&#9;2\""));
+
+        let parsed = JunitTestParser::from(xml)
+            .parse()
+            .expect("Unable to parse written report");
+        assert_eq!(
+            parsed[0].failure_message.as_deref(),
+            Some("expected:\r\n\t1\nbut was:\n\t2")
+        );
+    }
+}
@@ -0,0 +1,185 @@
+use crate::models::{
+    test_report::{PrettyPrint, ReportBuilder},
+    test_result::TestResult,
+    test_status::TestStatus,
+};
+use serde::Serialize;
+
+use super::html::escape_html;
+
+/// Struct that defines the content of a Matrix `m.room.message` event
+#[derive(Serialize)]
+pub struct MatrixReport {
+    /// `m.notice`, as recommended by the Matrix spec for automated messages
+    pub msgtype: String,
+    /// plain text fallback of the message
+    pub body: String,
+    /// format of the `formatted_body` field
+    pub format: String,
+    /// HTML version of the message
+    pub formatted_body: String,
+}
+
+/// Pretty print implementation for the Matrix report type
+impl PrettyPrint for MatrixReport {
+    fn to_string_pretty(&self) -> String {
+        serde_json::to_string_pretty(&self).expect("unable to serialize report to JSON")
+    }
+}
+
+/// Turns test results into a Matrix report
+impl From<ReportBuilder> for MatrixReport {
+    fn from(value: ReportBuilder) -> Self {
+        let summary = format!(
+            "{} failed, {} passed, {} skipped",
+            value.summary.failed, value.summary.passed, value.summary.skipped
+        );
+
+        let mut body = format!("{}\n{}\n", value.title, summary);
+        let mut formatted_body = format!(
+            "<h4>{}</h4>\n<p>{}</p>\n",
+            escape_html(&value.title),
+            summary
+        );
+
+        if value.test_results.is_empty() {
+            body.push_str("\n⚠️ unable to find test results\n");
+            formatted_body.push_str("<p>⚠️ unable to find test results</p>\n");
+        } else {
+            body.push('\n');
+            formatted_body.push_str("<ul>\n");
+            for t in value.test_results.iter() {
+                body.push_str(&test_to_plain_text(t));
+                formatted_body.push_str(&test_to_html(t));
+            }
+            formatted_body.push_str("</ul>\n");
+        }
+
+        if let Some(link) = value.link {
+            body.push_str(&format!("\nView details: {}\n", link));
+            formatted_body.push_str(&format!(
+                "<p><a href=\"{}\">View details</a></p>\n",
+                escape_html(link.as_str())
+            ));
+        }
+
+        MatrixReport {
+            msgtype: "m.notice".to_string(),
+            body,
+            format: "org.matrix.custom.html".to_string(),
+            formatted_body,
+        }
+    }
+}
+
+fn test_to_plain_text(test_result: &TestResult) -> String {
+    let time = test_result.execution_time.unwrap_or(0.0);
+    match test_result.status {
+        TestStatus::Passed => format!("✅ {} passed ({}s)\n", test_result.name, time),
+        TestStatus::Skipped => format!("⏭️ {} was skipped\n", test_result.name),
+        TestStatus::Failed => format!(
+            "❌ {} failed ({}s): {}\n",
+            test_result.name,
+            time,
+            test_result
+                .failure_message
+                .clone()
+                .unwrap_or("⚠️ missing failure message".to_string())
+        ),
+    }
+}
+
+fn test_to_html(test_result: &TestResult) -> String {
+    let name = escape_html(&test_result.name);
+    let time = test_result.execution_time.unwrap_or(0.0);
+    match test_result.status {
+        TestStatus::Passed => {
+            format!("<li>✅ <em>{name}</em> <strong>passed</strong> (<code>{time}s</code>)</li>\n")
+        }
+        TestStatus::Skipped => format!("<li>⏭️ <em>{name}</em> was <strong>skipped</strong></li>\n"),
+        TestStatus::Failed => format!(
+            "<li>❌ <em>{name}</em> <strong>failed</strong> (<code>{time}s</code>):<pre><code>{}</code></pre></li>\n",
+            escape_html(
+                &test_result
+                    .failure_message
+                    .clone()
+                    .unwrap_or("⚠️ missing failure message".to_string())
+            )
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{
+        test_report::{PrettyPrint, ReportBuilder},
+        test_result::TestResult,
+        test_status::TestStatus,
+    };
+    use assert_json::assert_json;
+    use indoc::indoc;
+    use url::Url;
+
+    use super::MatrixReport;
+
+    #[test]
+    fn should_create_report_in_matrix_format_empty() {
+        let report = ReportBuilder::new()
+            .with_title("An empty Matrix report".to_string())
+            .build::<MatrixReport>();
+
+        assert_json!(report.to_string_pretty().as_str(), {
+                "msgtype": "m.notice",
+                "body": "An empty Matrix report\n0 failed, 0 passed, 0 skipped\n\n⚠️ unable to find test results\n",
+                "format": "org.matrix.custom.html",
+                "formatted_body": "<h4>An empty Matrix report</h4>\n<p>0 failed, 0 passed, 0 skipped</p>\n<p>⚠️ unable to find test results</p>\n"
+            }
+        );
+    }
+
+    #[test]
+    fn should_create_report_in_matrix_format() {
+        let report = ReportBuilder::new()
+            .with_title("A <Matrix> report".to_string())
+            .include_skipped()
+            .with_test_results(vec![
+                TestResult::builder()
+                    .with_name("a test skipped".to_string())
+                    .with_status(TestStatus::Skipped)
+                    .build(),
+                TestResult::builder()
+                    .with_name("a test failed".to_string())
+                    .with_status(TestStatus::Failed)
+                    .with_failure_message("expected <1> but was <2>".to_string())
+                    .with_execution_time(1.2)
+                    .build(),
+            ])
+            .with_link(Url::parse("http://localhost/run/123").expect("unable to parse url"))
+            .build::<MatrixReport>();
+
+        assert_eq!(
+            report.body,
+            indoc! {"
+                A <Matrix> report
+                1 failed, 0 passed, 1 skipped
+
+                ❌ a test failed failed (1.2s): expected <1> but was <2>
+                ⏭️ a test skipped was skipped
+
+                View details: http://localhost/run/123
+            "}
+        );
+        assert_eq!(
+            report.formatted_body,
+            indoc! {"
+                <h4>A &lt;Matrix&gt; report</h4>
+                <p>1 failed, 0 passed, 1 skipped</p>
+                <ul>
+                <li>❌ <em>a test failed</em> <strong>failed</strong> (<code>1.2s</code>):<pre><code>expected &lt;1&gt; but was &lt;2&gt;</code></pre></li>
+                <li>⏭️ <em>a test skipped</em> was <strong>skipped</strong></li>
+                </ul>
+                <p><a href=\"http://localhost/run/123\">View details</a></p>
+            "}
+        );
+    }
+}
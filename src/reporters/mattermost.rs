@@ -0,0 +1,207 @@
+use crate::models::{
+    test_report::{PrettyPrint, ReportBuilder},
+    test_summary::TestSummary,
+};
+use serde::Serialize;
+use url::Url;
+
+/// Struct that defines a Mattermost incoming webhook report
+#[derive(Serialize)]
+pub struct MattermostReport {
+    pub text: String,
+    pub attachments: Vec<Attachment>,
+}
+
+/// Struct that defines a message attachment, in the format shared by Mattermost and Rocket.Chat
+#[derive(Serialize)]
+pub struct Attachment {
+    pub fallback: String,
+    pub color: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title_link: Option<Url>,
+    pub text: String,
+    pub fields: Vec<Field>,
+}
+
+/// Struct that defines a field of a message attachment, rendered as a small table cell
+#[derive(Serialize)]
+pub struct Field {
+    pub short: bool,
+    pub title: String,
+    pub value: String,
+}
+
+impl Attachment {
+    /// Builds an attachment colored by outcome, with a field per test status
+    pub(crate) fn new(
+        title: &str,
+        summary: &TestSummary,
+        test_messages: Vec<String>,
+        link: Option<Url>,
+    ) -> Attachment {
+        let color = if summary.failed > 0 {
+            "#d00000"
+        } else if summary.total() == 0 {
+            "#808080"
+        } else {
+            "#2eb886"
+        };
+
+        let text = if test_messages.is_empty() {
+            "⚠️ unable to find test results".to_string()
+        } else {
+            test_messages.join("\n")
+        };
+
+        let fields = vec![
+            Field {
+                short: true,
+                title: "Failed".to_string(),
+                value: summary.failed.to_string(),
+            },
+            Field {
+                short: true,
+                title: "Passed".to_string(),
+                value: summary.passed.to_string(),
+            },
+            Field {
+                short: true,
+                title: "Skipped".to_string(),
+                value: summary.skipped.to_string(),
+            },
+            Field {
+                short: true,
+                title: "Duration".to_string(),
+                value: format!("{}s", summary.execution_time),
+            },
+        ];
+
+        Attachment {
+            fallback: format!(
+                "{}: {} failed, {} passed, {} skipped",
+                title, summary.failed, summary.passed, summary.skipped
+            ),
+            color: color.to_string(),
+            title: link.as_ref().map(|_| "View details".to_string()),
+            title_link: link,
+            text,
+            fields,
+        }
+    }
+}
+
+/// Pretty print implementation for the Mattermost report type
+impl PrettyPrint for MattermostReport {
+    fn to_string_pretty(&self) -> String {
+        serde_json::to_string_pretty(&self).expect("unable to serialize report to JSON")
+    }
+}
+
+/// Turns test results into a Mattermost report
+impl From<ReportBuilder> for MattermostReport {
+    fn from(value: ReportBuilder) -> Self {
+        let test_messages = value
+            .test_results
+            .iter()
+            .map(|t| t.to_commonmark_string())
+            .collect();
+
+        MattermostReport {
+            text: format!("#### {}", value.title),
+            attachments: vec![Attachment::new(
+                &value.title,
+                &value.summary,
+                test_messages,
+                value.link,
+            )],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{
+        test_report::{PrettyPrint, ReportBuilder},
+        test_result::TestResult,
+        test_status::TestStatus,
+    };
+    use assert_json::assert_json;
+    use url::Url;
+
+    use super::MattermostReport;
+
+    #[test]
+    fn should_create_report_in_mattermost_format_empty() {
+        let report = ReportBuilder::new()
+            .with_title("An empty Mattermost report".to_string())
+            .build::<MattermostReport>();
+
+        assert_json!(report.to_string_pretty().as_str(), {
+                "text": "#### An empty Mattermost report",
+                "attachments": [
+                    {
+                        "fallback": "An empty Mattermost report: 0 failed, 0 passed, 0 skipped",
+                        "color": "#808080",
+                        "text": "⚠️ unable to find test results",
+                        "fields": [
+                            { "short": true, "title": "Failed", "value": "0" },
+                            { "short": true, "title": "Passed", "value": "0" },
+                            { "short": true, "title": "Skipped", "value": "0" },
+                            { "short": true, "title": "Duration", "value": "0s" }
+                        ]
+                    }
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn should_create_report_in_mattermost_format() {
+        let test_failed = TestResult::builder()
+            .with_name("a test failed".to_string())
+            .with_status(TestStatus::Failed)
+            .with_failure_message("A failure".to_string())
+            .with_execution_time(1.2)
+            .build();
+        let test_passed = TestResult::builder()
+            .with_name("a test passed".to_string())
+            .with_status(TestStatus::Passed)
+            .with_execution_time(3.3)
+            .build();
+
+        let report = ReportBuilder::new()
+            .with_title("A Mattermost report".to_string())
+            .include_passed()
+            .with_test_results(vec![test_passed.clone(), test_failed.clone()])
+            .with_link(Url::parse("http://localhost/run/123").expect("unable to parse url"))
+            .build::<MattermostReport>();
+
+        let expected_text = format!(
+            "{}\n{}",
+            test_failed.to_commonmark_string(),
+            test_passed.to_commonmark_string()
+        );
+
+        assert_json!(report.to_string_pretty().as_str(), {
+                "text": "#### A Mattermost report",
+                "attachments": [
+                    {
+                        "fallback": "A Mattermost report: 1 failed, 1 passed, 0 skipped",
+                        "color": "#d00000",
+                        "title": "View details",
+                        "title_link": "http://localhost/run/123",
+                        "text": expected_text.as_str(),
+                        "fields": [
+                            { "short": true, "title": "Failed", "value": "1" },
+                            { "short": true, "title": "Passed", "value": "1" },
+                            { "short": true, "title": "Skipped", "value": "0" },
+                            { "short": true, "title": "Duration", "value": "4.5s" }
+                        ]
+                    }
+                ]
+            }
+        );
+    }
+}
@@ -1,2 +1,42 @@
+/// Includes models and logic that helps rendering SVG status badges
+pub mod badge;
+/// Includes models and logic that helps building DingTalk custom robot messages
+pub mod dingtalk;
+/// Includes models and logic that helps building MIME email reports
+pub mod email;
+/// Includes models and logic that helps building Feishu/Lark interactive card messages
+pub mod feishu;
+/// Includes models and logic that helps building GitHub workflow commands and check run annotations
+pub mod github;
+/// Includes models and logic that helps building GitLab Code Quality reports and merge request notes
+pub mod gitlab;
+/// Includes models and logic that helps building self-contained HTML reports
+pub mod html;
+/// Includes models and logic that helps building Jira issue creation payloads
+pub mod jira;
+/// Includes models and logic that helps exporting test results as canonical JSON
+pub mod json;
+/// Includes models and logic that helps writing test results back into a JUnit XML document
+pub mod junit;
+/// Includes models and logic that helps building Matrix room messages
+pub mod matrix;
+/// Includes models and logic that helps building reports for Mattermost incoming webhooks
+pub mod mattermost;
+/// Includes models and logic that helps exporting a test run as an OpenTelemetry trace
+pub mod otlp;
+/// Includes models and logic that helps exporting test metrics in Prometheus exposition format
+pub mod prometheus;
+/// Includes the object-safe reporter trait, and the registry that helps choosing reporters at runtime
+pub mod registry;
+/// Includes models and logic that helps building reports for Rocket.Chat incoming webhooks
+pub mod rocketchat;
 /// Includes models and logic that helps building reports in Slack format
 pub mod slack;
+/// Includes models and logic that helps exporting test results as CSV and TSV tables
+pub mod tabular;
+/// Includes models and logic that helps building reports for the Telegram Bot API
+pub mod telegram;
+/// Includes models and logic that helps building Webex messages with adaptive cards
+pub mod webex;
+/// Includes models and logic that helps building Zulip stream messages
+pub mod zulip;
@@ -0,0 +1,434 @@
+use crate::models::{
+    test_report::{PrettyPrint, ReportBuilder},
+    test_result::{group_by_suite, TestResult},
+    test_status::TestStatus,
+};
+use serde::Serialize;
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// OTLP span kind of all the spans of the trace
+const SPAN_KIND_INTERNAL: u8 = 1;
+
+/// Struct that defines a test run as an OTLP trace: a root span for the run, with a child span
+/// per suite and a grandchild span per test. Test reports carry durations but no timestamps,
+/// so suites and tests are laid out one after the other, starting from the given start time.
+/// Only the test statuses included in the report builder get a span, so passed and skipped tests
+/// should be included to inspect the whole run.
+pub struct OtlpTraceReport {
+    /// the title of the report, used as name of the root span
+    pub title: String,
+    /// the reported test results, grouped by suite name in order of appearance
+    pub suites: Vec<(String, Vec<TestResult>)>,
+    /// start time of the run. Defaults to the current time minus the run duration
+    pub start_time: SystemTime,
+    /// hex encoded, 16 bytes long, trace identifier
+    pub trace_id: String,
+}
+
+/// Struct that defines the body of an OTLP trace export request, in OTLP JSON encoding
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportTraceServiceRequest {
+    pub resource_spans: Vec<ResourceSpans>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceSpans {
+    pub resource: Resource,
+    pub scope_spans: Vec<ScopeSpans>,
+}
+
+#[derive(Serialize)]
+pub struct Resource {
+    pub attributes: Vec<KeyValue>,
+}
+
+#[derive(Serialize)]
+pub struct ScopeSpans {
+    pub scope: InstrumentationScope,
+    pub spans: Vec<Span>,
+}
+
+#[derive(Serialize)]
+pub struct InstrumentationScope {
+    pub name: String,
+    pub version: String,
+}
+
+/// Struct that defines an OTLP span. Identifiers are hex encoded and timestamps are expressed in
+/// nanoseconds since the epoch, encoded as strings as mandated by the OTLP JSON encoding
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Span {
+    pub trace_id: String,
+    pub span_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_span_id: Option<String>,
+    pub name: String,
+    pub kind: u8,
+    pub start_time_unix_nano: String,
+    pub end_time_unix_nano: String,
+    pub attributes: Vec<KeyValue>,
+    pub status: Status,
+}
+
+/// Struct that defines the status of a span
+#[derive(Serialize)]
+pub struct Status {
+    pub code: StatusCode,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// Enum that defines the OTLP status codes
+#[derive(Serialize, Clone, Copy, PartialEq, Debug)]
+#[serde(into = "u8")]
+pub enum StatusCode {
+    Unset,
+    Ok,
+    Error,
+}
+
+impl From<StatusCode> for u8 {
+    fn from(value: StatusCode) -> Self {
+        match value {
+            StatusCode::Unset => 0,
+            StatusCode::Ok => 1,
+            StatusCode::Error => 2,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct KeyValue {
+    pub key: String,
+    pub value: AnyValue,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AnyValue {
+    StringValue(String),
+}
+
+impl OtlpTraceReport {
+    /// Overrides the start time of the run
+    pub fn with_start_time(mut self, start_time: SystemTime) -> OtlpTraceReport {
+        self.start_time = start_time;
+        self
+    }
+
+    /// Overrides the trace identifier, for instance to link the run to the trace of the CI job
+    pub fn with_trace_id(mut self, trace_id: String) -> OtlpTraceReport {
+        self.trace_id = trace_id;
+        self
+    }
+
+    /// Utility that lays out the spans of the run into an export request
+    pub fn to_export_request(&self) -> ExportTraceServiceRequest {
+        let root_span_id = random_hex(8);
+        let mut spans: Vec<Span> = vec![];
+        let mut suite_start = self.start_time;
+
+        for (suite_name, tests) in self.suites.iter() {
+            let suite_span_id = random_hex(8);
+            let mut test_start = suite_start;
+
+            for t in tests.iter() {
+                let test_end = test_start.checked_add(duration(t)).unwrap_or(test_start);
+                let (code, message) = match t.status {
+                    TestStatus::Failed => (StatusCode::Error, t.failure_message.clone()),
+                    TestStatus::Passed => (StatusCode::Ok, None),
+                    TestStatus::Skipped => (StatusCode::Unset, None),
+                };
+                let mut attributes = vec![
+                    attribute("test.case.name", &t.name),
+                    attribute("test.suite.name", suite_name),
+                    attribute("testvox.test.status", &t.status.to_string().to_lowercase()),
+                ];
+                if let Some(classname) = &t.classname {
+                    attributes.push(attribute("code.namespace", classname));
+                }
+
+                spans.push(self.span(
+                    random_hex(8),
+                    Some(suite_span_id.clone()),
+                    t.name.clone(),
+                    (test_start, test_end),
+                    attributes,
+                    Status { code, message },
+                ));
+                test_start = test_end;
+            }
+
+            let failed = tests.iter().any(|t| t.status == TestStatus::Failed);
+            spans.push(self.span(
+                suite_span_id,
+                Some(root_span_id.clone()),
+                suite_name.clone(),
+                (suite_start, test_start),
+                vec![attribute("test.suite.name", suite_name)],
+                status(failed),
+            ));
+            suite_start = test_start;
+        }
+
+        let failed = self
+            .suites
+            .iter()
+            .flat_map(|(_, tests)| tests.iter())
+            .any(|t| t.status == TestStatus::Failed);
+        spans.push(self.span(
+            root_span_id,
+            None,
+            self.title.clone(),
+            (self.start_time, suite_start),
+            vec![],
+            status(failed),
+        ));
+
+        ExportTraceServiceRequest {
+            resource_spans: vec![ResourceSpans {
+                resource: Resource {
+                    attributes: vec![attribute("service.name", "testvox")],
+                },
+                scope_spans: vec![ScopeSpans {
+                    scope: InstrumentationScope {
+                        name: env!("CARGO_PKG_NAME").to_string(),
+                        version: env!("CARGO_PKG_VERSION").to_string(),
+                    },
+                    spans,
+                }],
+            }],
+        }
+    }
+
+    fn span(
+        &self,
+        span_id: String,
+        parent_span_id: Option<String>,
+        name: String,
+        (start, end): (SystemTime, SystemTime),
+        attributes: Vec<KeyValue>,
+        status: Status,
+    ) -> Span {
+        Span {
+            trace_id: self.trace_id.clone(),
+            span_id,
+            parent_span_id,
+            name,
+            kind: SPAN_KIND_INTERNAL,
+            start_time_unix_nano: unix_nanos(start),
+            end_time_unix_nano: unix_nanos(end),
+            attributes,
+            status,
+        }
+    }
+}
+
+/// Pretty print implementation for the OTLP trace report type, yielding OTLP JSON
+impl PrettyPrint for OtlpTraceReport {
+    fn to_string_pretty(&self) -> String {
+        serde_json::to_string_pretty(&self.to_export_request())
+            .expect("unable to serialize report to JSON")
+    }
+}
+
+/// Turns test results into an OTLP trace report
+impl From<ReportBuilder> for OtlpTraceReport {
+    fn from(value: ReportBuilder) -> Self {
+        // absurd durations, as found in some reports, must not overflow
+        let run_duration = value
+            .test_results
+            .iter()
+            .map(duration)
+            .fold(Duration::ZERO, Duration::saturating_add);
+
+        let suites = group_by_suite(value.test_results);
+
+        OtlpTraceReport {
+            title: value.title,
+            suites,
+            start_time: SystemTime::now()
+                .checked_sub(run_duration)
+                .unwrap_or(UNIX_EPOCH),
+            trace_id: random_hex(16),
+        }
+    }
+}
+
+fn duration(test_result: &TestResult) -> Duration {
+    Duration::try_from_secs_f32(test_result.execution_time.unwrap_or(0.0)).unwrap_or_default()
+}
+
+fn status(failed: bool) -> Status {
+    Status {
+        code: if failed {
+            StatusCode::Error
+        } else {
+            StatusCode::Ok
+        },
+        message: None,
+    }
+}
+
+fn attribute(key: &str, value: &str) -> KeyValue {
+    KeyValue {
+        key: key.to_string(),
+        value: AnyValue::StringValue(value.to_string()),
+    }
+}
+
+fn unix_nanos(time: SystemTime) -> String {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos()
+        .to_string()
+}
+
+/// Yields a random identifier of the given length in bytes, hex encoded. Relies on the random
+/// keys of the standard library hasher, as trace identifiers only need to be unique.
+fn random_hex(bytes: usize) -> String {
+    let mut hex = String::with_capacity(bytes * 2);
+    while hex.len() < bytes * 2 {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u128(
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos(),
+        );
+        hex.push_str(&format!("{:016x}", hasher.finish()));
+    }
+    hex.truncate(bytes * 2);
+    hex
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{
+        test_report::{PrettyPrint, ReportBuilder},
+        test_result::TestResult,
+        test_status::TestStatus,
+    };
+    use assert_json::assert_json;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::{OtlpTraceReport, StatusCode};
+
+    fn sample_report() -> OtlpTraceReport {
+        ReportBuilder::new()
+            .with_title("A test run".to_string())
+            .include_passed()
+            .with_test_results(vec![
+                TestResult::builder()
+                    .with_name("a test passed".to_string())
+                    .with_suite_name("Suite.A".to_string())
+                    .with_status(TestStatus::Passed)
+                    .with_execution_time(1.5)
+                    .build(),
+                TestResult::builder()
+                    .with_name("a test failed".to_string())
+                    .with_suite_name("Suite.B".to_string())
+                    .with_classname("b.Test".to_string())
+                    .with_status(TestStatus::Failed)
+                    .with_failure_message("A failure".to_string())
+                    .with_execution_time(0.5)
+                    .build(),
+            ])
+            .build::<OtlpTraceReport>()
+            .with_start_time(UNIX_EPOCH + Duration::from_secs(1_700_000_000))
+            .with_trace_id("5b8efff798038103d269b633813fc60c".to_string())
+    }
+
+    #[test]
+    fn should_lay_out_suites_and_tests_as_spans() {
+        let request = sample_report().to_export_request();
+        let spans = &request.resource_spans[0].scope_spans[0].spans;
+
+        assert_eq!(spans.len(), 5);
+        assert!(spans
+            .iter()
+            .all(|s| s.trace_id == "5b8efff798038103d269b633813fc60c"));
+        assert!(spans.iter().all(|s| s.span_id.len() == 16));
+
+        let root = spans.iter().find(|s| s.name == "A test run").unwrap();
+        assert!(root.parent_span_id.is_none());
+        assert_eq!(root.start_time_unix_nano, "1700000000000000000");
+        assert_eq!(root.end_time_unix_nano, "1700000002000000000");
+        assert_eq!(root.status.code, StatusCode::Error);
+
+        // failed tests come first, as results are ordered by status
+        let suite_b = spans.iter().find(|s| s.name == "Suite.B").unwrap();
+        assert_eq!(suite_b.parent_span_id, Some(root.span_id.clone()));
+        assert_eq!(suite_b.start_time_unix_nano, "1700000000000000000");
+        assert_eq!(suite_b.end_time_unix_nano, "1700000000500000000");
+
+        let failed = spans.iter().find(|s| s.name == "a test failed").unwrap();
+        assert_eq!(failed.parent_span_id, Some(suite_b.span_id.clone()));
+        assert_eq!(failed.status.code, StatusCode::Error);
+        assert_eq!(failed.status.message, Some("A failure".to_string()));
+
+        let passed = spans.iter().find(|s| s.name == "a test passed").unwrap();
+        assert_eq!(passed.start_time_unix_nano, "1700000000500000000");
+        assert_eq!(passed.end_time_unix_nano, "1700000002000000000");
+        assert_eq!(passed.status.code, StatusCode::Ok);
+    }
+
+    #[test]
+    fn should_create_report_in_otlp_json_format() {
+        let report = sample_report();
+        let json = report.to_string_pretty();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let failed = value["resourceSpans"][0]["scopeSpans"][0]["spans"][0].to_string();
+
+        assert_json!(failed.as_str(), {
+                "traceId": "5b8efff798038103d269b633813fc60c",
+                "name": "a test failed",
+                "kind": 1,
+                "startTimeUnixNano": "1700000000000000000",
+                "endTimeUnixNano": "1700000000500000000",
+                "attributes": [
+                    { "key": "test.case.name", "value": { "stringValue": "a test failed" } },
+                    { "key": "test.suite.name", "value": { "stringValue": "Suite.B" } },
+                    { "key": "testvox.test.status", "value": { "stringValue": "failed" } },
+                    { "key": "code.namespace", "value": { "stringValue": "b.Test" } }
+                ],
+                "status": { "code": 2, "message": "A failure" }
+            }
+        );
+        assert_eq!(
+            value["resourceSpans"][0]["resource"]["attributes"][0]["value"]["stringValue"],
+            "testvox"
+        );
+    }
+
+    #[test]
+    fn should_not_overflow_with_absurd_durations() {
+        let test_result = |name: &str| {
+            TestResult::builder()
+                .with_name(name.to_string())
+                .with_status(TestStatus::Passed)
+                .with_execution_time(1e19)
+                .build()
+        };
+
+        let report = ReportBuilder::new()
+            .include_passed()
+            .with_test_results(vec![test_result("a test"), test_result("another test")])
+            .build::<OtlpTraceReport>();
+
+        assert_eq!(report.start_time, UNIX_EPOCH);
+        assert_eq!(
+            report.to_export_request().resource_spans[0].scope_spans[0]
+                .spans
+                .len(),
+            4
+        );
+    }
+}
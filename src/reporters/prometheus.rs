@@ -0,0 +1,236 @@
+use crate::models::{
+    test_report::{PrettyPrint, ReportBuilder},
+    test_result::TestResult,
+    test_summary::TestSummary,
+};
+
+/// Struct that defines a report in Prometheus exposition format, suitable for node_exporter's
+/// textfile collector. Every metric carries a `report` label with the report title, so that
+/// several reports can be exported from the same host.
+pub struct PrometheusReport {
+    /// the title of the report
+    pub title: String,
+    /// counters over all the parsed test results
+    pub summary: TestSummary,
+    /// counters over all the parsed test results, by suite name
+    pub suite_summaries: Vec<(String, TestSummary)>,
+    /// the reported test results
+    pub test_results: Vec<TestResult>,
+    /// maximum number of per-test duration gauges, disabled when `None`
+    pub test_durations_limit: Option<usize>,
+}
+
+impl PrometheusReport {
+    /// Enables per-test duration gauges for the slowest `limit` reported tests. The limit bounds
+    /// the cardinality of the `name` label. Only the test statuses included in the report builder
+    /// are considered, so passed tests should be included to track the slowest tests overall.
+    pub fn with_test_durations(mut self, limit: usize) -> PrometheusReport {
+        self.test_durations_limit = Some(limit);
+        self
+    }
+}
+
+/// Turns test results into a Prometheus report
+impl From<ReportBuilder> for PrometheusReport {
+    fn from(value: ReportBuilder) -> Self {
+        PrometheusReport {
+            title: value.title,
+            summary: value.summary,
+            suite_summaries: value.suite_summaries,
+            test_results: value.test_results,
+            test_durations_limit: None,
+        }
+    }
+}
+
+/// Pretty print implementation for the Prometheus report type
+impl PrettyPrint for PrometheusReport {
+    fn to_string_pretty(&self) -> String {
+        let report = format!("report=\"{}\"", escape_label_value(&self.title));
+
+        let mut metrics = String::new();
+
+        metrics.push_str(&header("testvox_tests", "Number of tests, by status"));
+        for (status, count) in [
+            ("failed", self.summary.failed),
+            ("passed", self.summary.passed),
+            ("skipped", self.summary.skipped),
+        ] {
+            metrics.push_str(&format!(
+                "testvox_tests{{{report},status=\"{status}\"}} {count}\n"
+            ));
+        }
+
+        metrics.push_str(&header(
+            "testvox_duration_seconds",
+            "Total execution time of the tests",
+        ));
+        metrics.push_str(&format!(
+            "testvox_duration_seconds{{{report}}} {}\n",
+            self.summary.execution_time
+        ));
+
+        if !self.suite_summaries.is_empty() {
+            metrics.push_str(&header(
+                "testvox_suite_duration_seconds",
+                "Execution time of the tests, by suite",
+            ));
+            for (suite, summary) in self.suite_summaries.iter() {
+                metrics.push_str(&format!(
+                    "testvox_suite_duration_seconds{{{report},suite=\"{}\"}} {}\n",
+                    escape_label_value(suite),
+                    summary.execution_time
+                ));
+            }
+        }
+
+        if let Some(limit) = self.test_durations_limit {
+            let mut slowest: Vec<&TestResult> = self
+                .test_results
+                .iter()
+                .filter(|t| t.execution_time.is_some())
+                .collect();
+            slowest.sort_by(|a, b| {
+                b.execution_time
+                    .unwrap_or(0.0)
+                    .total_cmp(&a.execution_time.unwrap_or(0.0))
+            });
+            slowest.truncate(limit);
+
+            if !slowest.is_empty() {
+                metrics.push_str(&header(
+                    "testvox_test_duration_seconds",
+                    "Execution time of the slowest tests",
+                ));
+            }
+            for t in slowest {
+                metrics.push_str(&format!(
+                    "testvox_test_duration_seconds{{{report},suite=\"{}\",classname=\"{}\",name=\"{}\"}} {}\n",
+                    escape_label_value(&t.suite_name.clone().unwrap_or_default()),
+                    escape_label_value(&t.classname.clone().unwrap_or_default()),
+                    escape_label_value(&t.name),
+                    t.execution_time.unwrap_or(0.0)
+                ));
+            }
+        }
+
+        metrics
+    }
+}
+
+fn header(name: &str, help: &str) -> String {
+    format!("# HELP {name} {help}\n# TYPE {name} gauge\n")
+}
+
+/// Escapes a label value as required by the exposition format
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{
+        test_report::{PrettyPrint, ReportBuilder},
+        test_result::TestResult,
+        test_status::TestStatus,
+    };
+    use indoc::indoc;
+
+    use super::PrometheusReport;
+
+    fn sample_builder() -> ReportBuilder {
+        ReportBuilder::new()
+            .with_title("my \"repo\"".to_string())
+            .include_passed()
+            .with_test_results(vec![
+                TestResult::builder()
+                    .with_name("a test passed".to_string())
+                    .with_suite_name("Suite.A".to_string())
+                    .with_status(TestStatus::Passed)
+                    .with_execution_time(1.5)
+                    .build(),
+                TestResult::builder()
+                    .with_name("a test skipped".to_string())
+                    .with_suite_name("Suite.A".to_string())
+                    .with_status(TestStatus::Skipped)
+                    .build(),
+                TestResult::builder()
+                    .with_name("a test failed".to_string())
+                    .with_suite_name("Suite.B".to_string())
+                    .with_status(TestStatus::Failed)
+                    .with_execution_time(0.5)
+                    .build(),
+                TestResult::builder()
+                    .with_name("another test passed".to_string())
+                    .with_suite_name("Suite.B".to_string())
+                    .with_status(TestStatus::Passed)
+                    .with_execution_time(2.0)
+                    .build(),
+            ])
+    }
+
+    #[test]
+    fn should_create_report_in_prometheus_format() {
+        let report = sample_builder().build::<PrometheusReport>();
+
+        assert_eq!(
+            report.to_string_pretty(),
+            indoc! {r#"
+                # HELP testvox_tests Number of tests, by status
+                # TYPE testvox_tests gauge
+                testvox_tests{report="my \"repo\"",status="failed"} 1
+                testvox_tests{report="my \"repo\"",status="passed"} 2
+                testvox_tests{report="my \"repo\"",status="skipped"} 1
+                # HELP testvox_duration_seconds Total execution time of the tests
+                # TYPE testvox_duration_seconds gauge
+                testvox_duration_seconds{report="my \"repo\""} 4
+                # HELP testvox_suite_duration_seconds Execution time of the tests, by suite
+                # TYPE testvox_suite_duration_seconds gauge
+                testvox_suite_duration_seconds{report="my \"repo\"",suite="Suite.A"} 1.5
+                testvox_suite_duration_seconds{report="my \"repo\"",suite="Suite.B"} 2.5
+            "#}
+        );
+    }
+
+    #[test]
+    fn should_include_bounded_per_test_durations() {
+        let report = sample_builder()
+            .build::<PrometheusReport>()
+            .with_test_durations(2);
+
+        assert!(report.to_string_pretty().ends_with(indoc! {r#"
+                # HELP testvox_test_duration_seconds Execution time of the slowest tests
+                # TYPE testvox_test_duration_seconds gauge
+                testvox_test_duration_seconds{report="my \"repo\"",suite="Suite.B",classname="",name="another test passed"} 2
+                testvox_test_duration_seconds{report="my \"repo\"",suite="Suite.A",classname="",name="a test passed"} 1.5
+            "#}));
+    }
+
+    #[test]
+    fn should_tell_apart_tests_with_the_same_name_in_different_classes() {
+        let test_result = |classname: &str| {
+            TestResult::builder()
+                .with_name("a".to_string())
+                .with_suite_name("S".to_string())
+                .with_classname(classname.to_string())
+                .with_status(TestStatus::Passed)
+                .with_execution_time(1.0)
+                .build()
+        };
+
+        let report = ReportBuilder::new()
+            .with_title("x".to_string())
+            .include_passed()
+            .with_test_results(vec![test_result("C1"), test_result("C2")])
+            .build::<PrometheusReport>()
+            .with_test_durations(2);
+
+        assert!(report.to_string_pretty().ends_with(indoc! {r#"
+                testvox_test_duration_seconds{report="x",suite="S",classname="C1",name="a"} 1
+                testvox_test_duration_seconds{report="x",suite="S",classname="C2",name="a"} 1
+            "#}));
+    }
+}
@@ -0,0 +1,306 @@
+use std::{collections::BTreeMap, marker::PhantomData};
+
+use thiserror::Error;
+
+use crate::models::test_report::{PrettyPrint, Report, ReportBuilder};
+
+use super::{
+    badge::BadgeReport,
+    dingtalk::DingTalkReport,
+    email::EmailReport,
+    feishu::FeishuReport,
+    github::{GithubAnnotations, GithubWorkflowCommands},
+    gitlab::{CodeQualityReport, MergeRequestNote},
+    html::HtmlReport,
+    json::JsonReport,
+    junit::JunitReport,
+    matrix::MatrixReport,
+    mattermost::MattermostReport,
+    otlp::OtlpTraceReport,
+    prometheus::PrometheusReport,
+    rocketchat::RocketChatReport,
+    slack::{SlackReport, SlackThreadReport},
+    tabular::{CsvReport, TsvReport},
+    telegram::TelegramReport,
+    webex::WebexReport,
+    zulip::ZulipReport,
+};
+
+/// Errors yielded when rendering reports
+#[derive(Debug, Error)]
+pub enum ReporterError {
+    /// no reporter is registered with the given name
+    #[error("unknown reporter '{name}', expected one of: {}", .known.join(", "))]
+    UnknownReporter { name: String, known: Vec<String> },
+    /// the reporter was unable to render the report
+    #[error("unable to render the report: {0}")]
+    Render(String),
+    /// the reporter needs a setting that wasn't given
+    #[error("the {reporter} reporter needs a {setting}")]
+    MissingSetting { reporter: String, setting: String },
+}
+
+/// Trait that defines a reporter that can be chosen at runtime: it renders a finalised report
+/// without consuming it, so that several reporters can render the same parsed test results.
+pub trait Reporter: Send + Sync {
+    /// Renders the given report into the bytes of the output format
+    fn render(&self, report: &Report) -> Result<Vec<u8>, ReporterError>;
+}
+
+/// Closures rendering reports are reporters as well, which comes in handy to register report types
+/// that need some settings, such as the label of a badge.
+impl<F> Reporter for F
+where
+    F: Fn(&Report) -> Result<Vec<u8>, ReporterError> + Send + Sync,
+{
+    fn render(&self, report: &Report) -> Result<Vec<u8>, ReporterError> {
+        self(report)
+    }
+}
+
+/// Adapter that turns any report type created out of a `ReportBuilder` into a `Reporter`
+pub struct BuilderReporter<T> {
+    report_type: PhantomData<fn() -> T>,
+}
+
+impl<T> BuilderReporter<T>
+where
+    T: From<ReportBuilder> + PrettyPrint,
+{
+    pub fn new() -> BuilderReporter<T> {
+        BuilderReporter {
+            report_type: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for BuilderReporter<T>
+where
+    T: From<ReportBuilder> + PrettyPrint,
+{
+    fn default() -> Self {
+        BuilderReporter::new()
+    }
+}
+
+impl<T> Reporter for BuilderReporter<T>
+where
+    T: From<ReportBuilder> + PrettyPrint,
+{
+    fn render(&self, report: &Report) -> Result<Vec<u8>, ReporterError> {
+        Ok(T::from(ReportBuilder::from(report))
+            .to_string_pretty()
+            .into_bytes())
+    }
+}
+
+/// Registry of reporters, by name. The default registry includes all the built-in reporters, and
+/// can be extended with reporters defined by other crates.
+pub struct ReporterRegistry {
+    reporters: BTreeMap<String, Box<dyn Reporter>>,
+}
+
+impl ReporterRegistry {
+    /// Yields a registry without any reporter
+    pub fn empty() -> ReporterRegistry {
+        ReporterRegistry {
+            reporters: BTreeMap::new(),
+        }
+    }
+
+    /// Registers a reporter with the given name, replacing any reporter with the same name
+    pub fn register<R>(&mut self, name: &str, reporter: R)
+    where
+        R: Reporter + 'static,
+    {
+        self.reporters.insert(name.to_string(), Box::new(reporter));
+    }
+
+    /// Registers a report type created out of a `ReportBuilder`, with the given name
+    pub fn register_report<T>(&mut self, name: &str)
+    where
+        T: From<ReportBuilder> + PrettyPrint + 'static,
+    {
+        self.register(name, BuilderReporter::<T>::new());
+    }
+
+    /// Yields the reporter with the given name
+    pub fn get(&self, name: &str) -> Option<&dyn Reporter> {
+        self.reporters.get(name).map(|r| r.as_ref())
+    }
+
+    /// Yields the names of the registered reporters, in alphabetical order
+    pub fn names(&self) -> Vec<&str> {
+        self.reporters.keys().map(|k| k.as_str()).collect()
+    }
+
+    /// Renders the given report with the reporter with the given name
+    pub fn render(&self, name: &str, report: &Report) -> Result<Vec<u8>, ReporterError> {
+        match self.get(name) {
+            Some(reporter) => reporter.render(report),
+            None => Err(ReporterError::UnknownReporter {
+                name: name.to_string(),
+                known: self.names().iter().map(|n| n.to_string()).collect(),
+            }),
+        }
+    }
+}
+
+impl Default for ReporterRegistry {
+    fn default() -> Self {
+        let mut registry = ReporterRegistry::empty();
+        registry.register_report::<BadgeReport>("badge");
+        registry.register_report::<CsvReport>("csv");
+        registry.register_report::<DingTalkReport>("dingtalk");
+        registry.register_report::<EmailReport>("email");
+        registry.register_report::<FeishuReport>("feishu");
+        registry.register_report::<GithubWorkflowCommands>("github");
+        registry.register_report::<GithubAnnotations>("github-annotations");
+        registry.register_report::<CodeQualityReport>("gitlab-codequality");
+        registry.register_report::<MergeRequestNote>("gitlab-note");
+        registry.register_report::<HtmlReport>("html");
+        // Jira rejects issues without a project key, so it has to be registered along with one
+        registry.register("jira", |_: &Report| {
+            Err(ReporterError::MissingSetting {
+                reporter: "jira".to_string(),
+                setting: "project key".to_string(),
+            })
+        });
+        registry.register_report::<JsonReport>("json");
+        registry.register_report::<JunitReport>("junit");
+        registry.register_report::<MatrixReport>("matrix");
+        registry.register_report::<MattermostReport>("mattermost");
+        registry.register_report::<OtlpTraceReport>("otlp");
+        registry.register_report::<PrometheusReport>("prometheus");
+        registry.register_report::<RocketChatReport>("rocketchat");
+        registry.register_report::<SlackReport>("slack");
+        registry.register_report::<SlackThreadReport>("slack-thread");
+        registry.register_report::<TelegramReport>("telegram");
+        registry.register_report::<TsvReport>("tsv");
+        registry.register_report::<WebexReport>("webex");
+        registry.register_report::<ZulipReport>("zulip");
+        registry
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{
+        test_report::{PrettyPrint, Report, ReportBuilder},
+        test_result::TestResult,
+        test_status::TestStatus,
+    };
+
+    use super::{Reporter, ReporterError, ReporterRegistry};
+    use crate::reporters::{prometheus::PrometheusReport, slack::SlackReport};
+
+    fn sample_builder() -> ReportBuilder {
+        ReportBuilder::new()
+            .with_title("A report".to_string())
+            .with_test_results(vec![
+                TestResult::builder()
+                    .with_name("a test passed".to_string())
+                    .with_suite_name("Suite.A".to_string())
+                    .with_status(TestStatus::Passed)
+                    .with_execution_time(1.5)
+                    .build(),
+                TestResult::builder()
+                    .with_name("a test failed".to_string())
+                    .with_suite_name("Suite.A".to_string())
+                    .with_status(TestStatus::Failed)
+                    .with_failure_message("A failure".to_string())
+                    .build(),
+            ])
+    }
+
+    #[test]
+    fn should_render_the_same_output_as_the_report_type() {
+        let report = sample_builder().build::<Report>();
+        let registry = ReporterRegistry::default();
+
+        for (name, expected) in [
+            (
+                "slack",
+                sample_builder().build::<SlackReport>().to_string_pretty(),
+            ),
+            (
+                "prometheus",
+                sample_builder()
+                    .build::<PrometheusReport>()
+                    .to_string_pretty(),
+            ),
+        ] {
+            let rendered = registry.render(name, &report).expect("unable to render");
+            assert_eq!(String::from_utf8(rendered).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn should_render_several_outputs_from_one_report() {
+        let report = sample_builder().build::<Report>();
+        let registry = ReporterRegistry::default();
+
+        // Jira needs a project key
+        for name in registry.names().into_iter().filter(|n| *n != "jira") {
+            let rendered = registry
+                .render(name, &report)
+                .unwrap_or_else(|e| panic!("unable to render {}: {}", name, e));
+            assert!(!rendered.is_empty(), "empty {} report", name);
+        }
+    }
+
+    #[test]
+    fn should_be_extensible_with_custom_reporters() {
+        struct Counter;
+
+        impl Reporter for Counter {
+            fn render(&self, report: &Report) -> Result<Vec<u8>, ReporterError> {
+                Ok(format!("{} failed", report.summary.failed).into_bytes())
+            }
+        }
+
+        let mut registry = ReporterRegistry::empty();
+        registry.register("counter", Counter);
+        registry.register("title", |report: &Report| {
+            Ok(report.title.clone().into_bytes())
+        });
+
+        assert_eq!(registry.names(), vec!["counter", "title"]);
+        assert_eq!(
+            registry
+                .render("counter", &sample_builder().build::<Report>())
+                .unwrap(),
+            b"1 failed"
+        );
+        assert_eq!(
+            registry
+                .render("title", &sample_builder().build::<Report>())
+                .unwrap(),
+            b"A report"
+        );
+    }
+
+    #[test]
+    fn should_fail_to_render_jira_issues_without_a_project_key() {
+        let error = ReporterRegistry::default()
+            .render("jira", &sample_builder().build::<Report>())
+            .expect_err("render should fail");
+
+        assert_eq!(error.to_string(), "the jira reporter needs a project key");
+    }
+
+    #[test]
+    fn should_fail_to_render_with_unknown_reporters() {
+        let mut registry = ReporterRegistry::empty();
+        registry.register_report::<SlackReport>("slack");
+
+        let error = registry
+            .render("nope", &sample_builder().build::<Report>())
+            .expect_err("render should fail");
+
+        assert_eq!(
+            error.to_string(),
+            "unknown reporter 'nope', expected one of: slack"
+        );
+    }
+}
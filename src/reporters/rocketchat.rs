@@ -0,0 +1,125 @@
+use crate::models::test_report::{PrettyPrint, ReportBuilder};
+use serde::Serialize;
+
+use super::mattermost::Attachment;
+
+/// Struct that defines a Rocket.Chat incoming webhook report. Rocket.Chat accepts the same
+/// attachments as Mattermost, but its markdown flavour is closer to Slack's one.
+#[derive(Serialize)]
+pub struct RocketChatReport {
+    pub text: String,
+    pub attachments: Vec<Attachment>,
+}
+
+/// Pretty print implementation for the Rocket.Chat report type
+impl PrettyPrint for RocketChatReport {
+    fn to_string_pretty(&self) -> String {
+        serde_json::to_string_pretty(&self).expect("unable to serialize report to JSON")
+    }
+}
+
+/// Turns test results into a Rocket.Chat report
+impl From<ReportBuilder> for RocketChatReport {
+    fn from(value: ReportBuilder) -> Self {
+        let test_messages = value
+            .test_results
+            .iter()
+            .map(|t| t.to_markdown_string())
+            .collect();
+
+        RocketChatReport {
+            text: format!("*{}*", value.title),
+            attachments: vec![Attachment::new(
+                &value.title,
+                &value.summary,
+                test_messages,
+                value.link,
+            )],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{
+        test_report::{PrettyPrint, ReportBuilder},
+        test_result::TestResult,
+        test_status::TestStatus,
+    };
+    use assert_json::assert_json;
+    use url::Url;
+
+    use super::RocketChatReport;
+
+    #[test]
+    fn should_create_report_in_rocketchat_format_empty() {
+        let report = ReportBuilder::new()
+            .with_title("An empty Rocket.Chat report".to_string())
+            .build::<RocketChatReport>();
+
+        assert_json!(report.to_string_pretty().as_str(), {
+                "text": "*An empty Rocket.Chat report*",
+                "attachments": [
+                    {
+                        "fallback": "An empty Rocket.Chat report: 0 failed, 0 passed, 0 skipped",
+                        "color": "#808080",
+                        "text": "⚠️ unable to find test results",
+                        "fields": [
+                            { "short": true, "title": "Failed", "value": "0" },
+                            { "short": true, "title": "Passed", "value": "0" },
+                            { "short": true, "title": "Skipped", "value": "0" },
+                            { "short": true, "title": "Duration", "value": "0s" }
+                        ]
+                    }
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn should_create_report_in_rocketchat_format() {
+        let test_failed = TestResult::builder()
+            .with_name("a test failed".to_string())
+            .with_status(TestStatus::Failed)
+            .with_failure_message("A failure".to_string())
+            .with_execution_time(1.2)
+            .build();
+        let test_skipped = TestResult::builder()
+            .with_name("a test skipped".to_string())
+            .with_status(TestStatus::Skipped)
+            .build();
+
+        let report = ReportBuilder::new()
+            .with_title("A Rocket.Chat report".to_string())
+            .include_skipped()
+            .with_test_results(vec![test_skipped.clone(), test_failed.clone()])
+            .with_link(Url::parse("http://localhost/run/123").expect("unable to parse url"))
+            .build::<RocketChatReport>();
+
+        let expected_text = format!(
+            "{}\n{}",
+            test_failed.to_markdown_string(),
+            test_skipped.to_markdown_string()
+        );
+
+        assert_json!(report.to_string_pretty().as_str(), {
+                "text": "*A Rocket.Chat report*",
+                "attachments": [
+                    {
+                        "fallback": "A Rocket.Chat report: 1 failed, 0 passed, 1 skipped",
+                        "color": "#d00000",
+                        "title": "View details",
+                        "title_link": "http://localhost/run/123",
+                        "text": expected_text.as_str(),
+                        "fields": [
+                            { "short": true, "title": "Failed", "value": "1" },
+                            { "short": true, "title": "Passed", "value": "0" },
+                            { "short": true, "title": "Skipped", "value": "1" },
+                            { "short": true, "title": "Duration", "value": "1.2s" }
+                        ]
+                    }
+                ]
+            }
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use crate::models::{
-    test_report::{PrettyPrint, ReportBuilder},
+    test_report::{truncate, PrettyPrint, ReportBuilder},
     test_result::TestResult,
+    test_summary::TestSummary,
 };
 use serde::Serialize;
 use url::Url;
@@ -52,12 +53,7 @@ impl PrettyPrint for SlackReport {
 /// Turns test results into a Slack report
 impl From<ReportBuilder> for SlackReport {
     fn from(value: ReportBuilder) -> Self {
-        let header_block = Block::Header {
-            text: PlainText {
-                text: value.title,
-                emoji: true,
-            },
-        };
+        let header_block = header(&value.title);
 
         let mut section_blocks: Vec<Block> = if value.test_results.is_empty() {
             vec![
@@ -69,20 +65,28 @@ impl From<ReportBuilder> for SlackReport {
                 },
             ]
         } else {
-            value
+            // the header, the link and the count of the left out tests take some of the blocks
+            let reserved_blocks = 3 + if value.link.is_some() { 2 } else { 0 };
+            let max_test_results = (MAX_BLOCKS - reserved_blocks) / 2;
+            let left_out = value.test_results.len().saturating_sub(max_test_results);
+
+            let mut blocks: Vec<Block> = value
                 .test_results
                 .into_iter()
-                .flat_map(|t| {
-                    vec![
-                        Block::Divider,
-                        Block::Section {
-                            text: MarkdownText {
-                                text: t.to_markdown_string(),
-                            },
+                .take(max_test_results)
+                .flat_map(Vec::<Block>::from)
+                .collect();
+            if left_out > 0 {
+                blocks.extend([
+                    Block::Divider,
+                    Block::Section {
+                        text: MarkdownText {
+                            text: format!("…and {} more", left_out),
                         },
-                    ]
-                })
-                .collect()
+                    },
+                ]);
+            }
+            blocks
         };
 
         let mut blocks = vec![header_block];
@@ -114,13 +118,147 @@ impl From<TestResult> for Vec<Block> {
             Block::Divider,
             Block::Section {
                 text: MarkdownText {
-                    text: test_result.to_markdown_string(),
+                    text: section_markdown(test_result),
                 },
             },
         ]
     }
 }
 
+/// Maximum number of blocks of a Slack message
+const MAX_BLOCKS: usize = 50;
+
+/// Maximum length of the text of a section block
+const MAX_SECTION_TEXT_LENGTH: usize = 3000;
+
+/// Maximum length of the text of a header block
+const MAX_HEADER_TEXT_LENGTH: usize = 150;
+
+/// Struct that defines a Slack report split into a thread, to keep busy channels readable: a
+/// compact summary as parent message, and the reported test results as replies, packed into as
+/// many messages as Block Kit limits require
+#[derive(Serialize)]
+pub struct SlackThreadReport {
+    pub parent: SlackMessage,
+    pub replies: Vec<SlackMessage>,
+}
+
+/// A single Slack message, with the text used in notifications
+#[derive(Serialize)]
+pub struct SlackMessage {
+    pub text: String,
+    pub blocks: Vec<Block>,
+}
+
+/// Pretty print implementation for the Slack thread report type
+impl PrettyPrint for SlackThreadReport {
+    fn to_string_pretty(&self) -> String {
+        serde_json::to_string_pretty(&self).expect("unable to serialize report to JSON")
+    }
+}
+
+/// Turns test results into a Slack thread report
+impl From<ReportBuilder> for SlackThreadReport {
+    fn from(value: ReportBuilder) -> Self {
+        let mut parent_blocks = vec![
+            header(&value.title),
+            Block::Section {
+                text: MarkdownText {
+                    text: summary_markdown(&value.summary),
+                },
+            },
+        ];
+        if let Some(link) = value.link {
+            parent_blocks.push(Block::Actions {
+                elements: vec![Element::Button {
+                    text: PlainText {
+                        text: ":link:  View details".to_string(),
+                        emoji: true,
+                    },
+                    url: link,
+                }],
+            });
+        }
+
+        let parent = SlackMessage {
+            text: format!(
+                "{}: {} failed, {} passed, {} skipped",
+                value.title, value.summary.failed, value.summary.passed, value.summary.skipped
+            ),
+            blocks: parent_blocks,
+        };
+
+        let mut reply_blocks: Vec<Vec<Block>> = vec![];
+        for test_result in value.test_results {
+            let blocks = Vec::<Block>::from(test_result);
+            match reply_blocks.last_mut() {
+                Some(last) if last.len() + blocks.len() <= MAX_BLOCKS => last.extend(blocks),
+                _ => reply_blocks.push(blocks),
+            }
+        }
+
+        let replies_count = reply_blocks.len();
+        let replies = reply_blocks
+            .into_iter()
+            .enumerate()
+            .map(|(i, blocks)| SlackMessage {
+                text: format!(
+                    "{}: test results ({}/{})",
+                    value.title,
+                    i + 1,
+                    replies_count
+                ),
+                blocks,
+            })
+            .collect();
+
+        SlackThreadReport { parent, replies }
+    }
+}
+
+fn summary_markdown(summary: &TestSummary) -> String {
+    if summary.total() == 0 {
+        return "⚠️ unable to find test results".to_string();
+    }
+
+    format!(
+        "❌ *{}* failed   ✅ *{}* passed   ⏭️ *{}* skipped   ⏱️ `{}s`",
+        summary.failed, summary.passed, summary.skipped, summary.execution_time
+    )
+}
+
+/// Yields a header block, with the text truncated to the length accepted by Slack
+fn header(title: &str) -> Block {
+    let mut text = title.to_string();
+    truncate(&mut text, MAX_HEADER_TEXT_LENGTH);
+    Block::Header {
+        text: PlainText { text, emoji: true },
+    }
+}
+
+/// Formats the test result within the length accepted by Slack for section texts. The failure
+/// message, and then the name, are truncated before formatting, so that markup is kept balanced.
+fn section_markdown(mut test_result: TestResult) -> String {
+    let mut excess = test_result
+        .to_markdown_string()
+        .chars()
+        .count()
+        .saturating_sub(MAX_SECTION_TEXT_LENGTH);
+
+    if let Some(message) = test_result.failure_message.as_mut().filter(|_| excess > 0) {
+        let length = message.chars().count();
+        // the ellipsis takes the place of a character
+        truncate(message, length.saturating_sub(excess).max(1));
+        excess = excess.saturating_sub(length - message.chars().count());
+    }
+    if excess > 0 {
+        let length = test_result.name.chars().count();
+        truncate(&mut test_result.name, length.saturating_sub(excess).max(1));
+    }
+
+    test_result.to_markdown_string()
+}
+
 #[cfg(test)]
 mod tests {
     use crate::models::{
@@ -131,7 +269,9 @@ mod tests {
     use assert_json::assert_json;
     use url::Url;
 
-    use super::SlackReport;
+    use super::{
+        SlackReport, SlackThreadReport, MAX_BLOCKS, MAX_HEADER_TEXT_LENGTH, MAX_SECTION_TEXT_LENGTH,
+    };
 
     #[test]
     fn should_create_report_in_slack_format_empty() {
@@ -258,4 +398,140 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn should_create_thread_report_with_summary_as_parent() {
+        let report = ReportBuilder::new()
+            .with_title("A Slack thread".to_string())
+            .with_test_results(vec![
+                TestResult::builder()
+                    .with_name("a test failed".to_string())
+                    .with_status(TestStatus::Failed)
+                    .with_failure_message("A failure".to_string())
+                    .with_execution_time(1.2)
+                    .build(),
+                TestResult::builder()
+                    .with_name("a test passed".to_string())
+                    .with_status(TestStatus::Passed)
+                    .with_execution_time(3.3)
+                    .build(),
+            ])
+            .with_link(Url::parse("http://localhost/run/123").expect("unable to parse url"))
+            .build::<SlackThreadReport>();
+
+        assert_json!(report.to_string_pretty().as_str(), {
+            "parent": {
+                "text": "A Slack thread: 1 failed, 1 passed, 0 skipped",
+                "blocks": [
+                    {
+                        "type": "header",
+                        "text": { "type": "plain_text", "text": "A Slack thread", "emoji": true }
+                    },
+                    {
+                        "type": "section",
+                        "text": {
+                            "type": "mrkdwn",
+                            "text": "❌ *1* failed   ✅ *1* passed   ⏭️ *0* skipped   ⏱️ `4.5s`"
+                        }
+                    },
+                    {
+                        "type": "actions",
+                        "elements": [
+                            {
+                                "type": "button",
+                                "text": { "type": "plain_text", "text": ":link:  View details", "emoji": true },
+                                "url": "http://localhost/run/123"
+                            }
+                        ]
+                    }
+                ]
+            },
+            "replies": [
+                {
+                    "text": "A Slack thread: test results (1/1)",
+                    "blocks": [
+                        { "type": "divider" },
+                        {
+                            "type": "section",
+                            "text": {
+                                "type": "mrkdwn",
+                                "text": "❌ _a test failed_ *failed* (`1.2s`): ```A failure```"
+                            }
+                        }
+                    ]
+                }
+            ]
+        });
+    }
+
+    #[test]
+    fn should_pack_thread_replies_within_block_kit_limits() {
+        let test_results = (0..60)
+            .map(|i| {
+                TestResult::builder()
+                    .with_name(format!("test {}", i))
+                    .with_status(TestStatus::Failed)
+                    .with_failure_message("x".repeat(5000))
+                    .build()
+            })
+            .collect();
+
+        let report = ReportBuilder::new()
+            .with_test_results(test_results)
+            .build::<SlackThreadReport>();
+
+        assert_eq!(report.replies.len(), 3);
+        assert_eq!(report.replies[0].blocks.len(), MAX_BLOCKS);
+        assert_eq!(report.replies[2].blocks.len(), 20);
+        assert_eq!(report.replies[2].text, ": test results (3/3)");
+        let json = report.to_string_pretty();
+        assert!(!json.contains(&"x".repeat(MAX_SECTION_TEXT_LENGTH)));
+        assert!(json.contains(&format!("{}…", "x".repeat(100))));
+    }
+
+    #[test]
+    fn should_truncate_texts_within_block_kit_limits_keeping_markup_balanced() {
+        let report = ReportBuilder::new()
+            .with_title("t".repeat(200))
+            .with_test_results(vec![TestResult::builder()
+                .with_name("a test failed".to_string())
+                .with_status(TestStatus::Failed)
+                .with_failure_message("x".repeat(5000))
+                .build()])
+            .build::<SlackReport>();
+
+        let json = serde_json::to_value(&report.blocks).expect("unable to serialize blocks");
+        let header = json[0]["text"]["text"].as_str().unwrap();
+        assert_eq!(header.chars().count(), MAX_HEADER_TEXT_LENGTH);
+        assert!(header.ends_with('…'));
+
+        let section = json[2]["text"]["text"].as_str().unwrap();
+        assert_eq!(section.chars().count(), MAX_SECTION_TEXT_LENGTH);
+        assert!(section.starts_with("❌ _a test failed_ *failed* (`0s`): ```xxx"));
+        assert!(section.ends_with("x…```"));
+    }
+
+    #[test]
+    fn should_cap_report_within_block_kit_limits() {
+        let test_results = (0..30)
+            .map(|i| {
+                TestResult::builder()
+                    .with_name(format!("test {}", i))
+                    .with_status(TestStatus::Failed)
+                    .build()
+            })
+            .collect();
+
+        let report = ReportBuilder::new()
+            .with_test_results(test_results)
+            .with_link(Url::parse("http://localhost/run/123").expect("unable to parse url"))
+            .build::<SlackReport>();
+
+        assert_eq!(report.blocks.len(), MAX_BLOCKS - 1);
+        let json = report.to_string_pretty();
+        assert!(json.contains("test 21"));
+        assert!(!json.contains("test 22"));
+        assert!(json.contains("…and 8 more"));
+        assert!(json.contains("View details"));
+    }
 }
@@ -0,0 +1,142 @@
+use crate::models::{
+    test_report::{PrettyPrint, ReportBuilder},
+    test_result::TestResult,
+};
+
+/// Columns of the tabular report, one row per test result
+const COLUMNS: [&str; 6] = [
+    "suite",
+    "classname",
+    "name",
+    "status",
+    "duration",
+    "failure_message",
+];
+
+/// Struct that defines a tabular report, separating values with the given delimiter
+pub struct TabularReport<const DELIMITER: char> {
+    pub test_results: Vec<TestResult>,
+}
+
+/// Comma separated values report, following RFC 4180
+pub type CsvReport = TabularReport<','>;
+
+/// Tab separated values report, escaping tabs and newlines with backslash sequences
+pub type TsvReport = TabularReport<'\t'>;
+
+/// Turns test results into a tabular report
+impl<const DELIMITER: char> From<ReportBuilder> for TabularReport<DELIMITER> {
+    fn from(value: ReportBuilder) -> Self {
+        TabularReport {
+            test_results: value.test_results,
+        }
+    }
+}
+
+/// Pretty print implementation for the tabular report types
+impl<const DELIMITER: char> PrettyPrint for TabularReport<DELIMITER> {
+    fn to_string_pretty(&self) -> String {
+        let mut rows = vec![COLUMNS.map(|c| c.to_string()).to_vec()];
+
+        rows.extend(self.test_results.iter().map(|t| {
+            vec![
+                t.suite_name.clone().unwrap_or_default(),
+                t.classname.clone().unwrap_or_default(),
+                t.name.clone(),
+                t.status.to_string(),
+                t.execution_time.map(|e| e.to_string()).unwrap_or_default(),
+                t.failure_message.clone().unwrap_or_default(),
+            ]
+        }));
+
+        let line_terminator = if DELIMITER == ',' { "\r\n" } else { "\n" };
+
+        rows.into_iter()
+            .map(|row| {
+                let fields: Vec<String> = row.iter().map(|f| escape::<DELIMITER>(f)).collect();
+                format!("{}{}", fields.join(&DELIMITER.to_string()), line_terminator)
+            })
+            .collect()
+    }
+}
+
+fn escape<const DELIMITER: char>(field: &str) -> String {
+    if DELIMITER == ',' {
+        // RFC 4180: fields containing delimiters, quotes or line breaks are quoted, and quotes
+        // are doubled
+        if field.contains([DELIMITER, '"', '\n', '\r']) {
+            format!("\"{}\"", field.replace('"', "\"\""))
+        } else {
+            field.to_string()
+        }
+    } else {
+        field
+            .replace('\\', "\\\\")
+            .replace(DELIMITER, "\\t")
+            .replace('\n', "\\n")
+            .replace('\r', "\\r")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{
+        test_report::{PrettyPrint, ReportBuilder},
+        test_result::TestResult,
+        test_status::TestStatus,
+    };
+
+    use super::{CsvReport, TsvReport};
+
+    fn sample_builder() -> ReportBuilder {
+        ReportBuilder::new()
+            .include_passed()
+            .include_skipped()
+            .with_test_results(vec![
+                TestResult::builder()
+                    .with_name("a test passed".to_string())
+                    .with_suite_name("Suite.A".to_string())
+                    .with_classname("a.Test".to_string())
+                    .with_status(TestStatus::Passed)
+                    .with_execution_time(1.5)
+                    .build(),
+                TestResult::builder()
+                    .with_name("a test, skipped".to_string())
+                    .with_status(TestStatus::Skipped)
+                    .build(),
+                TestResult::builder()
+                    .with_name("a test failed".to_string())
+                    .with_suite_name("Suite.B".to_string())
+                    .with_status(TestStatus::Failed)
+                    .with_failure_message("expected \"1\"\n\tbut was C:\\2".to_string())
+                    .with_execution_time(0.5)
+                    .build(),
+            ])
+    }
+
+    #[test]
+    fn should_create_report_in_csv_format() {
+        let report = sample_builder().build::<CsvReport>();
+
+        assert_eq!(
+            report.to_string_pretty(),
+            "suite,classname,name,status,duration,failure_message\r\n\
+             Suite.B,,a test failed,Failed,0.5,\"expected \"\"1\"\"\n\tbut was C:\\2\"\r\n\
+             ,,\"a test, skipped\",Skipped,,\r\n\
+             Suite.A,a.Test,a test passed,Passed,1.5,\r\n"
+        );
+    }
+
+    #[test]
+    fn should_create_report_in_tsv_format() {
+        let report = sample_builder().build::<TsvReport>();
+
+        assert_eq!(
+            report.to_string_pretty(),
+            "suite\tclassname\tname\tstatus\tduration\tfailure_message\n\
+             Suite.B\t\ta test failed\tFailed\t0.5\texpected \"1\"\\n\\tbut was C:\\\\2\n\
+             \t\ta test, skipped\tSkipped\t\t\n\
+             Suite.A\ta.Test\ta test passed\tPassed\t1.5\t\n"
+        );
+    }
+}
@@ -0,0 +1,311 @@
+use crate::models::{
+    test_report::{PrettyPrint, ReportBuilder},
+    test_result::TestResult,
+    test_status::TestStatus,
+};
+use serde::Serialize;
+use url::Url;
+
+/// Maximum length of a Telegram message, as counted by the Bot API
+pub const MAX_MESSAGE_LENGTH: usize = 4096;
+
+/// Characters that must be escaped anywhere in a `MarkdownV2` message, except in code entities
+const RESERVED_CHARACTERS: &[char] = &[
+    '_', '*', '[', ']', '(', ')', '~', '`', '>', '#', '+', '-', '=', '|', '{', '}', '.', '!', '\\',
+];
+
+/// Struct that defines a Telegram report, made of one or more `sendMessage` payloads so that
+/// each message stays within Telegram's length limit
+#[derive(Serialize)]
+pub struct TelegramReport {
+    pub messages: Vec<SendMessage>,
+}
+
+/// Struct that defines the payload of the Bot API `sendMessage` method
+#[derive(Serialize)]
+pub struct SendMessage {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chat_id: Option<String>,
+    pub text: String,
+    pub parse_mode: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reply_markup: Option<InlineKeyboardMarkup>,
+}
+
+/// Struct that defines an inline keyboard attached to a message
+#[derive(Serialize)]
+pub struct InlineKeyboardMarkup {
+    pub inline_keyboard: Vec<Vec<InlineKeyboardButton>>,
+}
+
+/// Struct that defines a button of an inline keyboard, opening the given url
+#[derive(Serialize)]
+pub struct InlineKeyboardButton {
+    pub text: String,
+    pub url: Url,
+}
+
+impl TelegramReport {
+    /// Sets the target chat of all the messages of the report
+    pub fn with_chat_id(mut self, chat_id: String) -> TelegramReport {
+        self.messages
+            .iter_mut()
+            .for_each(|m| m.chat_id = Some(chat_id.clone()));
+        self
+    }
+}
+
+/// Pretty print implementation for the Telegram report type, yielding a JSON array of payloads
+impl PrettyPrint for TelegramReport {
+    fn to_string_pretty(&self) -> String {
+        serde_json::to_string_pretty(&self.messages).expect("unable to serialize report to JSON")
+    }
+}
+
+/// Turns test results into a Telegram report
+impl From<ReportBuilder> for TelegramReport {
+    fn from(value: ReportBuilder) -> Self {
+        let mut entries = vec![format!(
+            "*{}*\n{} failed, {} passed, {} skipped\n",
+            escape_markdown_v2(&value.title),
+            value.summary.failed,
+            value.summary.passed,
+            value.summary.skipped
+        )];
+
+        if value.test_results.is_empty() {
+            entries.push(escape_markdown_v2("⚠️ unable to find test results"));
+        } else {
+            entries.extend(value.test_results.iter().map(test_to_markdown_v2));
+        }
+
+        // entries are packed into as few messages as possible, never splitting a single entry
+        let mut texts: Vec<String> = vec![];
+        let mut current = String::new();
+        for entry in entries {
+            if !current.is_empty() && length(&current) + 1 + length(&entry) > MAX_MESSAGE_LENGTH {
+                texts.push(current);
+                current = String::new();
+            }
+            if !current.is_empty() {
+                current.push('\n');
+            }
+            current.push_str(&entry);
+        }
+        texts.push(current);
+
+        let mut messages: Vec<SendMessage> = texts
+            .into_iter()
+            .map(|text| SendMessage {
+                chat_id: None,
+                text,
+                parse_mode: "MarkdownV2".to_string(),
+                reply_markup: None,
+            })
+            .collect();
+
+        if let (Some(link), Some(last)) = (value.link, messages.last_mut()) {
+            last.reply_markup = Some(InlineKeyboardMarkup {
+                inline_keyboard: vec![vec![InlineKeyboardButton {
+                    text: "🔗 View details".to_string(),
+                    url: link,
+                }]],
+            });
+        }
+
+        TelegramReport { messages }
+    }
+}
+
+fn test_to_markdown_v2(test_result: &TestResult) -> String {
+    let name = escape_markdown_v2(&test_result.name);
+    let time = test_result.execution_time.unwrap_or(0.0);
+
+    match test_result.status {
+        TestStatus::Passed => format!("✅ _{name}_ *passed* \\(`{time}s`\\)"),
+        TestStatus::Skipped => format!("⏭️ _{name}_ was *skipped*"),
+        TestStatus::Failed => {
+            let prefix = format!("❌ _{name}_ *failed* \\(`{time}s`\\):\n```\n");
+            let suffix = "\n```";
+            let failure_message = test_result
+                .failure_message
+                .clone()
+                .unwrap_or("⚠️ missing failure message".to_string());
+            let budget = MAX_MESSAGE_LENGTH.saturating_sub(length(&prefix) + length(suffix));
+
+            format!(
+                "{prefix}{}{suffix}",
+                escape_code_truncated(&failure_message, budget)
+            )
+        }
+    }
+}
+
+/// Length of a text as counted by Telegram, in UTF-16 code units
+fn length(text: &str) -> usize {
+    text.encode_utf16().count()
+}
+
+/// Escapes Telegram's reserved characters outside of code entities
+fn escape_markdown_v2(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if RESERVED_CHARACTERS.contains(&c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Escapes the content of a code entity, where only '`' and '\' are reserved, truncating it so
+/// that the escaped content does not exceed the given length
+fn escape_code_truncated(text: &str, max_length: usize) -> String {
+    let ellipsis = '…';
+    let mut escaped = String::with_capacity(text.len());
+    let mut escaped_length = 0;
+    let text_length = text.chars().count();
+
+    for (i, c) in text.chars().enumerate() {
+        let escape = c == '`' || c == '\\';
+        let char_length = c.len_utf16() + usize::from(escape);
+        // room for an ellipsis is kept, unless this is the very last character
+        let reserved = if i + 1 < text_length {
+            ellipsis.len_utf16()
+        } else {
+            0
+        };
+
+        if escaped_length + char_length + reserved > max_length {
+            escaped.push(ellipsis);
+            break;
+        }
+        if escape {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+        escaped_length += char_length;
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{
+        test_report::{PrettyPrint, ReportBuilder},
+        test_result::TestResult,
+        test_status::TestStatus,
+    };
+    use assert_json::assert_json;
+    use url::Url;
+
+    use super::{escape_markdown_v2, length, TelegramReport, MAX_MESSAGE_LENGTH};
+
+    #[test]
+    fn should_create_report_in_telegram_format() {
+        let report = ReportBuilder::new()
+            .with_title("Mobile app (nightly)".to_string())
+            .include_passed()
+            .with_test_results(vec![
+                TestResult::builder()
+                    .with_name("login_should_work".to_string())
+                    .with_status(TestStatus::Passed)
+                    .with_execution_time(3.3)
+                    .build(),
+                TestResult::builder()
+                    .with_name("logout_should_work".to_string())
+                    .with_status(TestStatus::Failed)
+                    .with_failure_message("expected `true` but was `false`".to_string())
+                    .with_execution_time(1.2)
+                    .build(),
+            ])
+            .with_link(Url::parse("http://localhost/run/123").expect("unable to parse url"))
+            .build::<TelegramReport>()
+            .with_chat_id("-100123".to_string());
+
+        assert_json!(report.to_string_pretty().as_str(), [
+                {
+                    "chat_id": "-100123",
+                    "text": "*Mobile app \\(nightly\\)*\n1 failed, 1 passed, 0 skipped\n\n❌ _logout\\_should\\_work_ *failed* \\(`1.2s`\\):\n```\nexpected \\`true\\` but was \\`false\\`\n```\n✅ _login\\_should\\_work_ *passed* \\(`3.3s`\\)",
+                    "parse_mode": "MarkdownV2",
+                    "reply_markup": {
+                        "inline_keyboard": [
+                            [
+                                {
+                                    "text": "🔗 View details",
+                                    "url": "http://localhost/run/123"
+                                }
+                            ]
+                        ]
+                    }
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn should_split_report_at_telegram_message_limit() {
+        let test_results = (0..200)
+            .map(|i| {
+                TestResult::builder()
+                    .with_name(format!("a very long test name that fills the message {i}"))
+                    .with_status(TestStatus::Failed)
+                    .with_failure_message("a failure".to_string())
+                    .build()
+            })
+            .collect();
+
+        let report = ReportBuilder::new()
+            .with_title("A long report".to_string())
+            .with_test_results(test_results)
+            .with_link(Url::parse("http://localhost/run/123").expect("unable to parse url"))
+            .build::<TelegramReport>();
+
+        assert!(report.messages.len() > 1);
+        assert!(report
+            .messages
+            .iter()
+            .all(|m| length(&m.text) <= MAX_MESSAGE_LENGTH));
+        assert!(report
+            .messages
+            .iter()
+            .all(|m| m.text.matches("```").count() % 2 == 0));
+        assert!(report.messages.last().unwrap().reply_markup.is_some());
+        assert!(report.messages[..report.messages.len() - 1]
+            .iter()
+            .all(|m| m.reply_markup.is_none()));
+        assert_eq!(
+            report
+                .messages
+                .iter()
+                .map(|m| m.text.matches("*failed*").count())
+                .sum::<usize>(),
+            200
+        );
+    }
+
+    #[test]
+    fn should_truncate_failure_messages_exceeding_telegram_message_limit() {
+        let report = ReportBuilder::new()
+            .with_title("A report".to_string())
+            .with_test_results(vec![TestResult::builder()
+                .with_name("a test".to_string())
+                .with_status(TestStatus::Failed)
+                .with_failure_message("`".repeat(5000))
+                .build()])
+            .build::<TelegramReport>();
+
+        assert_eq!(report.messages.len(), 2);
+        assert!(length(&report.messages[1].text) <= MAX_MESSAGE_LENGTH);
+        assert!(report.messages[1].text.ends_with("…\n```"));
+    }
+
+    #[test]
+    fn should_escape_telegram_reserved_characters() {
+        assert_eq!(
+            escape_markdown_v2("a_b*c[d](e)~f`g>h#i+j-k=l|m{n}o.p!q\\r"),
+            "a\\_b\\*c\\[d\\]\\(e\\)\\~f\\`g\\>h\\#i\\+j\\-k\\=l\\|m\\{n\\}o\\.p\\!q\\\\r"
+        );
+    }
+}
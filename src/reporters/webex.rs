@@ -0,0 +1,278 @@
+use crate::models::{
+    test_report::{PrettyPrint, ReportBuilder},
+    test_result::TestResult,
+    test_status::TestStatus,
+};
+use serde::Serialize;
+use url::Url;
+
+/// Struct that defines a Webex message, with a markdown fallback and an adaptive card attachment
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebexReport {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub room_id: Option<String>,
+    pub markdown: String,
+    pub attachments: Vec<Attachment>,
+}
+
+/// Struct that defines a Webex message attachment
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Attachment {
+    pub content_type: String,
+    pub content: AdaptiveCard,
+}
+
+/// Struct that defines an adaptive card
+#[derive(Serialize)]
+pub struct AdaptiveCard {
+    #[serde(rename = "$schema")]
+    pub schema: String,
+    #[serde(rename = "type")]
+    pub card_type: String,
+    pub version: String,
+    pub body: Vec<CardElement>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub actions: Vec<CardAction>,
+}
+
+/// Enum that defines the variant of adaptive card elements
+#[derive(Serialize)]
+#[serde(tag = "type")]
+pub enum CardElement {
+    TextBlock {
+        text: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        size: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        weight: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        color: Option<String>,
+        wrap: bool,
+    },
+    FactSet {
+        facts: Vec<Fact>,
+    },
+}
+
+/// Struct that defines a key-value pair of a fact set
+#[derive(Serialize)]
+pub struct Fact {
+    pub title: String,
+    pub value: String,
+}
+
+/// Enum that defines the variant of adaptive card actions
+#[derive(Serialize)]
+#[serde(tag = "type")]
+pub enum CardAction {
+    #[serde(rename = "Action.OpenUrl")]
+    OpenUrl { title: String, url: Url },
+}
+
+impl WebexReport {
+    /// Sets the room the message should be sent to
+    pub fn with_room_id(mut self, room_id: String) -> WebexReport {
+        self.room_id = Some(room_id);
+        self
+    }
+}
+
+/// Pretty print implementation for the Webex report type
+impl PrettyPrint for WebexReport {
+    fn to_string_pretty(&self) -> String {
+        serde_json::to_string_pretty(&self).expect("unable to serialize report to JSON")
+    }
+}
+
+/// Turns test results into a Webex report
+impl From<ReportBuilder> for WebexReport {
+    fn from(value: ReportBuilder) -> Self {
+        let summary = format!(
+            "{} failed, {} passed, {} skipped",
+            value.summary.failed, value.summary.passed, value.summary.skipped
+        );
+
+        let mut markdown = format!("**{}**  \n{}\n\n", value.title, summary);
+        let mut body = vec![
+            CardElement::TextBlock {
+                text: value.title,
+                size: Some("Medium".to_string()),
+                weight: Some("Bolder".to_string()),
+                color: None,
+                wrap: true,
+            },
+            CardElement::FactSet {
+                facts: vec![
+                    fact("Failed", value.summary.failed.to_string()),
+                    fact("Passed", value.summary.passed.to_string()),
+                    fact("Skipped", value.summary.skipped.to_string()),
+                    fact("Duration", format!("{}s", value.summary.execution_time)),
+                ],
+            },
+        ];
+
+        if value.test_results.is_empty() {
+            markdown.push_str("⚠️ unable to find test results\n");
+            body.push(text_block(
+                "⚠️ unable to find test results".to_string(),
+                None,
+            ));
+        } else {
+            for t in value.test_results.iter() {
+                markdown.push_str(&format!("- {}\n", t.to_commonmark_string()));
+                body.push(test_to_text_block(t));
+            }
+        }
+
+        let mut actions = vec![];
+        if let Some(link) = value.link {
+            markdown.push_str(&format!("\n[View details]({})\n", link));
+            actions.push(CardAction::OpenUrl {
+                title: "View details".to_string(),
+                url: link,
+            });
+        }
+
+        WebexReport {
+            room_id: None,
+            markdown,
+            attachments: vec![Attachment {
+                content_type: "application/vnd.microsoft.card.adaptive".to_string(),
+                content: AdaptiveCard {
+                    schema: "http://adaptivecards.io/schemas/adaptive-card.json".to_string(),
+                    card_type: "AdaptiveCard".to_string(),
+                    version: "1.3".to_string(),
+                    body,
+                    actions,
+                },
+            }],
+        }
+    }
+}
+
+fn fact(title: &str, value: String) -> Fact {
+    Fact {
+        title: title.to_string(),
+        value,
+    }
+}
+
+fn text_block(text: String, color: Option<&str>) -> CardElement {
+    CardElement::TextBlock {
+        text,
+        size: None,
+        weight: None,
+        color: color.map(|c| c.to_string()),
+        wrap: true,
+    }
+}
+
+fn test_to_text_block(test_result: &TestResult) -> CardElement {
+    let time = test_result.execution_time.unwrap_or(0.0);
+    match test_result.status {
+        TestStatus::Passed => text_block(
+            format!("✅ {} passed ({}s)", test_result.name, time),
+            Some("Good"),
+        ),
+        TestStatus::Skipped => text_block(
+            format!("⏭️ {} was skipped", test_result.name),
+            Some("Warning"),
+        ),
+        TestStatus::Failed => text_block(
+            format!(
+                "❌ {} failed ({}s): {}",
+                test_result.name,
+                time,
+                test_result
+                    .failure_message
+                    .clone()
+                    .unwrap_or("⚠️ missing failure message".to_string())
+            ),
+            Some("Attention"),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{
+        test_report::{PrettyPrint, ReportBuilder},
+        test_result::TestResult,
+        test_status::TestStatus,
+    };
+    use assert_json::assert_json;
+    use url::Url;
+
+    use super::WebexReport;
+
+    #[test]
+    fn should_create_report_in_webex_format() {
+        let test_failed = TestResult::builder()
+            .with_name("a test failed".to_string())
+            .with_status(TestStatus::Failed)
+            .with_failure_message("A failure".to_string())
+            .with_execution_time(1.2)
+            .build();
+
+        let report = ReportBuilder::new()
+            .with_title("A Webex report".to_string())
+            .with_test_results(vec![test_failed.clone()])
+            .with_link(Url::parse("http://localhost/run/123").expect("unable to parse url"))
+            .build::<WebexReport>()
+            .with_room_id("a-room".to_string());
+
+        let expected_markdown = format!(
+            "**A Webex report**  \n1 failed, 0 passed, 0 skipped\n\n- {}\n\n[View details](http://localhost/run/123)\n",
+            test_failed.to_commonmark_string()
+        );
+
+        assert_json!(report.to_string_pretty().as_str(), {
+                "roomId": "a-room",
+                "markdown": expected_markdown.as_str(),
+                "attachments": [
+                    {
+                        "contentType": "application/vnd.microsoft.card.adaptive",
+                        "content": {
+                            "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+                            "type": "AdaptiveCard",
+                            "version": "1.3",
+                            "body": [
+                                {
+                                    "type": "TextBlock",
+                                    "text": "A Webex report",
+                                    "size": "Medium",
+                                    "weight": "Bolder",
+                                    "wrap": true
+                                },
+                                {
+                                    "type": "FactSet",
+                                    "facts": [
+                                        { "title": "Failed", "value": "1" },
+                                        { "title": "Passed", "value": "0" },
+                                        { "title": "Skipped", "value": "0" },
+                                        { "title": "Duration", "value": "1.2s" }
+                                    ]
+                                },
+                                {
+                                    "type": "TextBlock",
+                                    "text": "❌ a test failed failed (1.2s): A failure",
+                                    "color": "Attention",
+                                    "wrap": true
+                                }
+                            ],
+                            "actions": [
+                                {
+                                    "type": "Action.OpenUrl",
+                                    "title": "View details",
+                                    "url": "http://localhost/run/123"
+                                }
+                            ]
+                        }
+                    }
+                ]
+            }
+        );
+    }
+}
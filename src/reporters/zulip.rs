@@ -0,0 +1,127 @@
+use crate::models::test_report::{truncate, PrettyPrint, ReportBuilder};
+use serde::Serialize;
+
+/// Maximum length of a Zulip topic
+pub const MAX_TOPIC_LENGTH: usize = 60;
+
+/// Struct that defines a Zulip stream message. Its fields match the parameters of the
+/// `POST /api/v1/messages` endpoint, which expects them form-encoded.
+#[derive(Serialize)]
+pub struct ZulipReport {
+    #[serde(rename = "type")]
+    pub message_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    pub topic: String,
+    pub content: String,
+}
+
+impl ZulipReport {
+    /// Sets the stream the message should be sent to
+    pub fn with_stream(mut self, stream: String) -> ZulipReport {
+        self.to = Some(stream);
+        self
+    }
+
+    /// Overrides the topic of the message, which defaults to the report title so that each
+    /// pipeline gets its own topic
+    pub fn with_topic(mut self, mut topic: String) -> ZulipReport {
+        truncate(&mut topic, MAX_TOPIC_LENGTH);
+        self.topic = topic;
+        self
+    }
+}
+
+/// Pretty print implementation for the Zulip report type
+impl PrettyPrint for ZulipReport {
+    fn to_string_pretty(&self) -> String {
+        serde_json::to_string_pretty(&self).expect("unable to serialize report to JSON")
+    }
+}
+
+/// Turns test results into a Zulip report
+impl From<ReportBuilder> for ZulipReport {
+    fn from(value: ReportBuilder) -> Self {
+        let mut content = format!(
+            "**{}**\n{} failed, {} passed, {} skipped\n\n",
+            value.title, value.summary.failed, value.summary.passed, value.summary.skipped
+        );
+
+        if value.test_results.is_empty() {
+            content.push_str("⚠️ unable to find test results\n");
+        } else {
+            value
+                .test_results
+                .iter()
+                .for_each(|t| content.push_str(&format!("* {}\n", t.to_commonmark_string())));
+        }
+
+        if let Some(link) = value.link {
+            content.push_str(&format!("\n[View details]({})\n", link));
+        }
+
+        let mut topic = value.title;
+        truncate(&mut topic, MAX_TOPIC_LENGTH);
+
+        ZulipReport {
+            message_type: "stream".to_string(),
+            to: None,
+            topic,
+            content,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{
+        test_report::{PrettyPrint, ReportBuilder},
+        test_result::TestResult,
+        test_status::TestStatus,
+    };
+    use assert_json::assert_json;
+    use url::Url;
+
+    use super::{ZulipReport, MAX_TOPIC_LENGTH};
+
+    #[test]
+    fn should_create_report_in_zulip_format() {
+        let test_failed = TestResult::builder()
+            .with_name("a test failed".to_string())
+            .with_status(TestStatus::Failed)
+            .with_failure_message("A failure".to_string())
+            .with_execution_time(1.2)
+            .build();
+
+        let report = ReportBuilder::new()
+            .with_title("A Zulip report".to_string())
+            .with_test_results(vec![test_failed.clone()])
+            .with_link(Url::parse("http://localhost/run/123").expect("unable to parse url"))
+            .build::<ZulipReport>()
+            .with_stream("ci".to_string());
+
+        let expected_content = format!(
+            "**A Zulip report**\n1 failed, 0 passed, 0 skipped\n\n* {}\n\n[View details](http://localhost/run/123)\n",
+            test_failed.to_commonmark_string()
+        );
+
+        assert_json!(report.to_string_pretty().as_str(), {
+                "type": "stream",
+                "to": "ci",
+                "topic": "A Zulip report",
+                "content": expected_content.as_str()
+            }
+        );
+    }
+
+    #[test]
+    fn should_truncate_long_topics() {
+        let report = ReportBuilder::new()
+            .with_title("A Zulip report".to_string())
+            .build::<ZulipReport>()
+            .with_topic("a-pipeline-".repeat(10));
+
+        assert_eq!(report.topic.chars().count(), MAX_TOPIC_LENGTH);
+        assert!(report.topic.ends_with('…'));
+    }
+}
@@ -0,0 +1,12 @@
+/// Includes logic that helps exporting OTLP traces to an OTLP/HTTP collector
+#[cfg(feature = "otlp")]
+pub mod otlp;
+/// Includes logic that helps retrying requests that failed temporarily
+#[cfg(feature = "send")]
+pub mod retry;
+/// Includes logic that helps sending reports to Slack
+#[cfg(feature = "send")]
+pub mod slack;
+/// Includes logic that helps sending reports, rendered from templates, to arbitrary webhooks
+#[cfg(feature = "webhook")]
+pub mod webhook;
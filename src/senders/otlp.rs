@@ -0,0 +1,141 @@
+use anyhow::{bail, Context, Result};
+use std::time::Duration;
+use url::Url;
+
+use crate::reporters::otlp::OtlpTraceReport;
+
+/// Timeout of the requests to the OTLP endpoint
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Path of the traces signal, relative to the OTLP/HTTP endpoint
+const TRACES_PATH: &str = "v1/traces";
+
+/// Exports the trace of a test run to an OTLP/HTTP collector, using the OTLP JSON encoding. The
+/// `/v1/traces` path is appended to the endpoint unless already present, as OTLP exporters do.
+pub fn export(
+    report: &OtlpTraceReport,
+    endpoint: &Url,
+    headers: &[(String, String)],
+) -> Result<()> {
+    let url = traces_url(endpoint)?;
+    let body = serde_json::to_string(&report.to_export_request())?;
+
+    let mut request = ureq::post(url.as_str())
+        .timeout(TIMEOUT)
+        .set("Content-Type", "application/json");
+    for (name, value) in headers {
+        request = request.set(name, value);
+    }
+
+    match request.send_string(&body) {
+        Ok(_) => Ok(()),
+        Err(ureq::Error::Status(code, response)) => bail!(
+            "OTLP endpoint {} responded with status {}: {}",
+            url,
+            code,
+            response.into_string().unwrap_or_default()
+        ),
+        Err(e) => Err(e).with_context(|| format!("Unable to reach OTLP endpoint {}", url)),
+    }
+}
+
+fn traces_url(endpoint: &Url) -> Result<Url> {
+    if endpoint.path().trim_end_matches('/').ends_with(TRACES_PATH) {
+        return Ok(endpoint.clone());
+    }
+
+    let mut base = endpoint.clone();
+    if !base.path().ends_with('/') {
+        base.set_path(&format!("{}/", base.path()));
+    }
+    Ok(base.join(TRACES_PATH)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        models::{test_report::ReportBuilder, test_result::TestResult, test_status::TestStatus},
+        reporters::otlp::OtlpTraceReport,
+    };
+    use mockito::Matcher;
+    use url::Url;
+
+    use super::{export, traces_url};
+
+    fn sample_report() -> OtlpTraceReport {
+        ReportBuilder::new()
+            .with_title("A test run".to_string())
+            .with_test_results(vec![TestResult::builder()
+                .with_name("a test failed".to_string())
+                .with_status(TestStatus::Failed)
+                .build()])
+            .build::<OtlpTraceReport>()
+            .with_trace_id("5b8efff798038103d269b633813fc60c".to_string())
+    }
+
+    #[test]
+    fn should_export_trace_to_otlp_http_endpoint() {
+        let mut collector = mockito::Server::new();
+        let mock = collector
+            .mock("POST", "/v1/traces")
+            .match_header("content-type", "application/json")
+            .match_header("authorization", "Bearer a-token")
+            .match_body(Matcher::Regex(
+                "\"traceId\":\"5b8efff798038103d269b633813fc60c\"".to_string(),
+            ))
+            .with_status(200)
+            .create();
+
+        export(
+            &sample_report(),
+            &Url::parse(&collector.url()).unwrap(),
+            &[("Authorization".to_string(), "Bearer a-token".to_string())],
+        )
+        .expect("unable to export trace");
+
+        mock.assert();
+    }
+
+    #[test]
+    fn should_fail_when_collector_rejects_the_trace() {
+        let mut collector = mockito::Server::new();
+        collector
+            .mock("POST", "/v1/traces")
+            .with_status(400)
+            .with_body("bad trace")
+            .create();
+
+        let error = export(
+            &sample_report(),
+            &Url::parse(&collector.url()).unwrap(),
+            &[],
+        )
+        .expect_err("export should fail");
+
+        assert!(error
+            .to_string()
+            .contains("responded with status 400: bad trace"));
+    }
+
+    #[test]
+    fn should_append_traces_path_to_endpoint() {
+        let traces = |endpoint: &str| {
+            traces_url(&Url::parse(endpoint).unwrap())
+                .unwrap()
+                .to_string()
+        };
+
+        assert_eq!(
+            traces("http://localhost:4318"),
+            "http://localhost:4318/v1/traces"
+        );
+        assert_eq!(
+            traces("http://localhost:4318/otlp/"),
+            "http://localhost:4318/otlp/v1/traces"
+        );
+        assert_eq!(
+            traces("http://localhost:4318/v1/traces"),
+            "http://localhost:4318/v1/traces"
+        );
+    }
+}
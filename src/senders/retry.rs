@@ -0,0 +1,90 @@
+use std::{thread, time::Duration};
+
+/// Struct that defines how failed requests are retried. Requests are retried when rate limited
+/// (429) or when the server fails (5xx), waiting for the delay in the `Retry-After` header when
+/// given, or for an exponentially growing backoff otherwise.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RetryPolicy {
+    /// maximum number of retries, after the first attempt
+    pub max_retries: u32,
+    /// delay before the first retry, doubled at every retry
+    pub initial_backoff: Duration,
+    /// upper bound of the delay between two attempts, `Retry-After` included
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_retries: 3,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Yields the delay before the given retry, starting from 0
+    fn delay(&self, retry: u32, retry_after: Option<&str>) -> Duration {
+        let delay = match retry_after.and_then(|r| r.trim().parse::<u64>().ok()) {
+            Some(seconds) => Duration::from_secs(seconds),
+            None => self
+                .initial_backoff
+                .saturating_mul(2_u32.saturating_pow(retry)),
+        };
+        delay.min(self.max_backoff)
+    }
+}
+
+/// Sends the given body with the request yielded by `request`, retrying it according to the
+/// given policy. A new request is yielded for every attempt, as requests are consumed when sent.
+pub(crate) fn with_retry<F>(
+    policy: &RetryPolicy,
+    request: F,
+    body: &str,
+) -> Result<ureq::Response, Box<ureq::Error>>
+where
+    F: Fn() -> ureq::Request,
+{
+    let mut retry = 0;
+    loop {
+        match request().send_string(body) {
+            Err(ureq::Error::Status(code, response))
+                if (code == 429 || code >= 500) && retry < policy.max_retries =>
+            {
+                thread::sleep(policy.delay(retry, response.header("Retry-After")));
+                retry += 1;
+            }
+            result => return result.map_err(Box::new),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::RetryPolicy;
+
+    #[test]
+    fn should_back_off_exponentially_up_to_the_maximum() {
+        let policy = RetryPolicy::default();
+
+        assert_eq!(policy.delay(0, None), Duration::from_secs(1));
+        assert_eq!(policy.delay(2, None), Duration::from_secs(4));
+        assert_eq!(policy.delay(10, None), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn should_honour_retry_after_up_to_the_maximum() {
+        let policy = RetryPolicy::default();
+
+        assert_eq!(policy.delay(0, Some("7")), Duration::from_secs(7));
+        assert_eq!(policy.delay(0, Some("3600")), Duration::from_secs(30));
+        // HTTP dates are not supported, falling back to the backoff
+        assert_eq!(
+            policy.delay(1, Some("Wed, 21 Oct 2015 07:28:00 GMT")),
+            Duration::from_secs(2)
+        );
+    }
+}
@@ -0,0 +1,407 @@
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use url::Url;
+
+use crate::reporters::slack::{Block, SlackMessage, SlackReport, SlackThreadReport};
+
+use super::retry::{with_retry, RetryPolicy};
+
+/// Timeout of the requests to Slack
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Base URL of the Slack Web API
+const WEB_API_URL: &str = "https://slack.com/api/";
+
+/// Sends a report to a Slack incoming webhook. Errors never include the webhook URL, as it
+/// embeds the secret that grants access to the channel.
+pub fn send(report: &SlackReport, webhook_url: &Url, retry_policy: &RetryPolicy) -> Result<()> {
+    let body = serde_json::to_string(report)?;
+
+    let result = with_retry(
+        retry_policy,
+        || {
+            ureq::post(webhook_url.as_str())
+                .timeout(TIMEOUT)
+                .set("Content-Type", "application/json")
+        },
+        &body,
+    );
+
+    match result.map_err(|e| *e) {
+        Ok(_) => Ok(()),
+        Err(ureq::Error::Status(code, response)) => bail!(
+            "Slack webhook responded with status {}: {}",
+            code,
+            response.into_string().unwrap_or_default()
+        ),
+        Err(ureq::Error::Transport(e)) => Err(anyhow::anyhow!("{}", e.kind())).context(format!(
+            "Unable to reach Slack webhook on {}",
+            host(webhook_url)
+        )),
+    }
+}
+
+/// Client of the Slack Web API, posting reports as a bot with the `chat:write` scope
+pub struct SlackWebApi {
+    api_url: Url,
+    token: String,
+    retry_policy: RetryPolicy,
+}
+
+/// Body of the `chat.postMessage` and `chat.update` requests
+#[derive(Serialize)]
+struct ChatMessage<'a> {
+    channel: &'a str,
+    text: &'a str,
+    blocks: &'a [Block],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ts: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thread_ts: Option<&'a str>,
+}
+
+/// Response of the `chat.postMessage` and `chat.update` methods
+#[derive(Deserialize)]
+struct ChatResponse {
+    ok: bool,
+    error: Option<String>,
+    channel: Option<String>,
+    ts: Option<String>,
+}
+
+impl SlackWebApi {
+    /// Yields a client authenticated with the given bot token
+    pub fn new(token: String) -> SlackWebApi {
+        SlackWebApi {
+            api_url: Url::parse(WEB_API_URL).expect("invalid Slack Web API URL"),
+            token,
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+
+    /// Overrides the base URL of the Web API
+    pub fn with_api_url(mut self, api_url: Url) -> SlackWebApi {
+        self.api_url = api_url;
+        self
+    }
+
+    /// Overrides the default retry policy
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> SlackWebApi {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Posts a thread report to the given channel, and yields the `ts` of the parent message. When
+    /// `update_ts` is given, the parent message updates that earlier message in place, such as a
+    /// "tests running…" one, rather than being posted anew.
+    pub fn post_thread(
+        &self,
+        report: &SlackThreadReport,
+        channel: &str,
+        update_ts: Option<&str>,
+    ) -> Result<String> {
+        let parent = match update_ts {
+            Some(ts) => self.call(
+                "chat.update",
+                &message(&report.parent, channel, Some(ts), None),
+            )?,
+            None => self.call(
+                "chat.postMessage",
+                &message(&report.parent, channel, None, None),
+            )?,
+        };
+
+        let parent_ts = parent
+            .ts
+            .context("Slack Web API did not yield the ts of the parent message")?;
+        // replies must address the channel by id, which the API yields even when given a name
+        let channel = parent.channel.as_deref().unwrap_or(channel);
+
+        for reply in report.replies.iter() {
+            self.call(
+                "chat.postMessage",
+                &message(reply, channel, None, Some(&parent_ts)),
+            )?;
+        }
+
+        Ok(parent_ts)
+    }
+
+    fn call(&self, method: &str, body: &ChatMessage) -> Result<ChatResponse> {
+        let url = self.api_url.join(method)?;
+        let body = serde_json::to_string(body)?;
+
+        let result = with_retry(
+            &self.retry_policy,
+            || {
+                ureq::post(url.as_str())
+                    .timeout(TIMEOUT)
+                    .set("Authorization", &format!("Bearer {}", self.token))
+                    .set("Content-Type", "application/json; charset=utf-8")
+            },
+            &body,
+        );
+
+        let response: ChatResponse = match result.map_err(|e| *e) {
+            Ok(response) => serde_json::from_str(&response.into_string()?)
+                .with_context(|| format!("Unable to read the response of Slack {}", method))?,
+            Err(ureq::Error::Status(code, response)) => bail!(
+                "Slack {} responded with status {}: {}",
+                method,
+                code,
+                response.into_string().unwrap_or_default()
+            ),
+            Err(e) => {
+                return Err(e).with_context(|| format!("Unable to reach Slack Web API on {}", url))
+            }
+        };
+
+        if !response.ok {
+            bail!(
+                "Slack {} failed: {}",
+                method,
+                response.error.as_deref().unwrap_or("unknown error")
+            );
+        }
+
+        Ok(response)
+    }
+}
+
+fn message<'a>(
+    message: &'a SlackMessage,
+    channel: &'a str,
+    ts: Option<&'a str>,
+    thread_ts: Option<&'a str>,
+) -> ChatMessage<'a> {
+    ChatMessage {
+        channel,
+        text: &message.text,
+        blocks: &message.blocks,
+        ts,
+        thread_ts,
+    }
+}
+
+fn host(url: &Url) -> &str {
+    url.host_str().unwrap_or("unknown host")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{
+        models::{test_report::ReportBuilder, test_result::TestResult, test_status::TestStatus},
+        reporters::slack::{SlackReport, SlackThreadReport},
+        senders::retry::RetryPolicy,
+    };
+    use mockito::Matcher;
+    use serde_json::json;
+    use url::Url;
+
+    use super::{send, SlackWebApi};
+
+    const WEBHOOK_PATH: &str = "/services/T000/B000/secret";
+
+    fn sample_report() -> SlackReport {
+        ReportBuilder::new()
+            .with_title("A test run".to_string())
+            .with_test_results(vec![TestResult::builder()
+                .with_name("a test failed".to_string())
+                .with_status(TestStatus::Failed)
+                .build()])
+            .build::<SlackReport>()
+    }
+
+    fn retry_policy() -> RetryPolicy {
+        RetryPolicy {
+            max_retries: 2,
+            initial_backoff: Duration::ZERO,
+            max_backoff: Duration::ZERO,
+        }
+    }
+
+    fn webhook_url(server: &mockito::Server) -> Url {
+        Url::parse(&format!("{}{}", server.url(), WEBHOOK_PATH)).unwrap()
+    }
+
+    #[test]
+    fn should_post_report_to_webhook() {
+        let mut slack = mockito::Server::new();
+        let mock = slack
+            .mock("POST", WEBHOOK_PATH)
+            .match_header("content-type", "application/json")
+            .match_body(Matcher::Regex("\"blocks\":\\[".to_string()))
+            .with_status(200)
+            .with_body("ok")
+            .create();
+
+        send(&sample_report(), &webhook_url(&slack), &retry_policy())
+            .expect("unable to send report");
+
+        mock.assert();
+    }
+
+    #[test]
+    fn should_retry_when_rate_limited_or_failing() {
+        let mut slack = mockito::Server::new();
+        let rate_limited = slack
+            .mock("POST", WEBHOOK_PATH)
+            .with_status(429)
+            .with_header("Retry-After", "0")
+            .expect(1)
+            .create();
+        let failing = slack
+            .mock("POST", WEBHOOK_PATH)
+            .with_status(503)
+            .expect(1)
+            .create();
+        let ok = slack
+            .mock("POST", WEBHOOK_PATH)
+            .with_status(200)
+            .with_body("ok")
+            .expect(1)
+            .create();
+
+        send(&sample_report(), &webhook_url(&slack), &retry_policy())
+            .expect("unable to send report");
+
+        rate_limited.assert();
+        failing.assert();
+        ok.assert();
+    }
+
+    #[test]
+    fn should_give_up_after_max_retries() {
+        let mut slack = mockito::Server::new();
+        let mock = slack
+            .mock("POST", WEBHOOK_PATH)
+            .with_status(500)
+            .with_body("internal error")
+            .expect(3)
+            .create();
+
+        let error = send(&sample_report(), &webhook_url(&slack), &retry_policy())
+            .expect_err("send should fail");
+
+        mock.assert();
+        assert_eq!(
+            error.to_string(),
+            "Slack webhook responded with status 500: internal error"
+        );
+    }
+
+    #[test]
+    fn should_not_retry_client_errors_nor_leak_the_webhook_url() {
+        let mut slack = mockito::Server::new();
+        let mock = slack
+            .mock("POST", WEBHOOK_PATH)
+            .with_status(404)
+            .with_body("no_team")
+            .expect(1)
+            .create();
+
+        let error = send(&sample_report(), &webhook_url(&slack), &retry_policy())
+            .expect_err("send should fail");
+
+        mock.assert();
+        assert!(!format!("{:#}", error).contains("secret"));
+    }
+
+    fn sample_thread_report() -> SlackThreadReport {
+        ReportBuilder::new()
+            .with_title("A test run".to_string())
+            .with_test_results(vec![TestResult::builder()
+                .with_name("a test failed".to_string())
+                .with_status(TestStatus::Failed)
+                .build()])
+            .build::<SlackThreadReport>()
+    }
+
+    fn web_api(server: &mockito::Server) -> SlackWebApi {
+        SlackWebApi::new("xoxb-token".to_string())
+            .with_api_url(Url::parse(&format!("{}/api/", server.url())).unwrap())
+            .with_retry_policy(retry_policy())
+    }
+
+    #[test]
+    fn should_post_summary_and_replies_in_a_thread() {
+        let mut slack = mockito::Server::new();
+        let parent = slack
+            .mock("POST", "/api/chat.postMessage")
+            .match_header("authorization", "Bearer xoxb-token")
+            .match_body(Matcher::PartialJson(json!({
+                "channel": "#ci",
+                "text": "A test run: 1 failed, 0 passed, 0 skipped"
+            })))
+            .with_body(r#"{"ok": true, "channel": "C123", "ts": "1700000000.000100"}"#)
+            .expect(1)
+            .create();
+        let reply = slack
+            .mock("POST", "/api/chat.postMessage")
+            .match_body(Matcher::PartialJson(json!({
+                "channel": "C123",
+                "thread_ts": "1700000000.000100"
+            })))
+            .with_body(r#"{"ok": true, "channel": "C123", "ts": "1700000000.000200"}"#)
+            .expect(1)
+            .create();
+
+        let ts = web_api(&slack)
+            .post_thread(&sample_thread_report(), "#ci", None)
+            .expect("unable to post thread");
+
+        assert_eq!(ts, "1700000000.000100");
+        parent.assert();
+        reply.assert();
+    }
+
+    #[test]
+    fn should_update_an_earlier_message_in_place() {
+        let mut slack = mockito::Server::new();
+        let update = slack
+            .mock("POST", "/api/chat.update")
+            .match_body(Matcher::PartialJson(json!({
+                "channel": "C123",
+                "ts": "1700000000.000001"
+            })))
+            .with_body(r#"{"ok": true, "channel": "C123", "ts": "1700000000.000001"}"#)
+            .expect(1)
+            .create();
+        let reply = slack
+            .mock("POST", "/api/chat.postMessage")
+            .match_body(Matcher::PartialJson(json!({
+                "thread_ts": "1700000000.000001"
+            })))
+            .with_body(r#"{"ok": true, "channel": "C123", "ts": "1700000000.000200"}"#)
+            .expect(1)
+            .create();
+
+        web_api(&slack)
+            .post_thread(&sample_thread_report(), "C123", Some("1700000000.000001"))
+            .expect("unable to post thread");
+
+        update.assert();
+        reply.assert();
+    }
+
+    #[test]
+    fn should_fail_when_the_web_api_yields_an_error() {
+        let mut slack = mockito::Server::new();
+        slack
+            .mock("POST", "/api/chat.postMessage")
+            .with_body(r#"{"ok": false, "error": "channel_not_found"}"#)
+            .create();
+
+        let error = web_api(&slack)
+            .post_thread(&sample_thread_report(), "#nope", None)
+            .expect_err("post should fail");
+
+        assert_eq!(
+            error.to_string(),
+            "Slack chat.postMessage failed: channel_not_found"
+        );
+    }
+}
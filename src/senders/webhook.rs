@@ -0,0 +1,254 @@
+use anyhow::{bail, Context, Result};
+use minijinja::{value::Serde, Environment, UndefinedBehavior, Value};
+use std::time::Duration;
+use url::Url;
+
+use crate::models::test_report::Report;
+
+use super::retry::{with_retry, RetryPolicy};
+
+/// Timeout of the requests to the webhook
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Struct that defines a webhook, to which reports are sent in the format described by a
+/// [minijinja](https://docs.rs/minijinja) template. Templates are rendered against the `Report`
+/// model, so they can refer to `title`, `link`, `summary` and `test_results`. Values embedded in
+/// JSON bodies should go through the `tojson` filter, that takes care of quoting and escaping.
+pub struct Webhook {
+    url: Url,
+    template: String,
+    headers: Vec<(String, String)>,
+    retry_policy: RetryPolicy,
+}
+
+impl Webhook {
+    /// Yields a webhook posting the given template, rendered, to the given URL
+    pub fn new(url: Url, template: String) -> Webhook {
+        Webhook {
+            url,
+            template,
+            headers: vec![],
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+
+    /// Adds a header to the requests. The content type defaults to `application/json`.
+    pub fn with_header(mut self, name: String, value: String) -> Webhook {
+        self.headers.push((name, value));
+        self
+    }
+
+    /// Overrides the default retry policy
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Webhook {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Renders the template against the given report. Referring to undefined values is an error,
+    /// so that typos in templates don't go unnoticed.
+    pub fn render(&self, report: &Report) -> Result<String> {
+        let mut env = Environment::new();
+        env.set_undefined_behavior(UndefinedBehavior::Strict);
+
+        env.render_str(&self.template, Value::from(Serde(report)))
+            .map_err(|e| anyhow::anyhow!("{:#}", e))
+            .context("Unable to render the webhook template")
+    }
+
+    /// Renders the template against the given report, and posts it to the webhook
+    pub fn send(&self, report: &Report) -> Result<()> {
+        let body = self.render(report)?;
+
+        let result = with_retry(
+            &self.retry_policy,
+            || {
+                let mut request = ureq::post(self.url.as_str())
+                    .timeout(TIMEOUT)
+                    .set("Content-Type", "application/json");
+                for (name, value) in self.headers.iter() {
+                    request = request.set(name, value);
+                }
+                request
+            },
+            &body,
+        );
+
+        match result.map_err(|e| *e) {
+            Ok(_) => Ok(()),
+            // webhook URLs often carry secrets, so errors only refer to their host
+            Err(ureq::Error::Status(code, response)) => bail!(
+                "Webhook on {} responded with status {}: {}",
+                host(&self.url),
+                code,
+                response.into_string().unwrap_or_default()
+            ),
+            Err(ureq::Error::Transport(e)) => Err(anyhow::anyhow!("{}", e.kind()))
+                .context(format!("Unable to reach webhook on {}", host(&self.url))),
+        }
+    }
+}
+
+fn host(url: &Url) -> &str {
+    url.host_str().unwrap_or("unknown host")
+}
+
+/// Parses a header given as `Name: value`
+pub fn parse_header(header: &str) -> Result<(String, String)> {
+    match header.split_once(':') {
+        Some((name, value)) if !name.trim().is_empty() => {
+            Ok((name.trim().to_string(), value.trim().to_string()))
+        }
+        _ => bail!("Invalid header '{}', expected 'Name: value'", header),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{
+        models::{
+            test_report::{Report, ReportBuilder},
+            test_result::TestResult,
+            test_status::TestStatus,
+        },
+        senders::retry::RetryPolicy,
+    };
+    use indoc::indoc;
+    use mockito::Matcher;
+    use serde_json::json;
+    use url::Url;
+
+    use super::{parse_header, Webhook};
+
+    const TEMPLATE: &str = indoc! {r#"
+        {
+          "message": {{ (title ~ ": " ~ summary.failed ~ " failed") | tojson }},
+          "failures": [{% for t in test_results %}{{ t.name | tojson }}{% if not loop.last %}, {% endif %}{% endfor %}]
+        }"#};
+
+    fn sample_report() -> Report {
+        ReportBuilder::new()
+            .with_title("Nightly \"e2e\"".to_string())
+            .include_passed()
+            .with_test_results(vec![
+                TestResult::builder()
+                    .with_name("a test passed".to_string())
+                    .with_status(TestStatus::Passed)
+                    .build(),
+                TestResult::builder()
+                    .with_name("a test failed".to_string())
+                    .with_status(TestStatus::Failed)
+                    .build(),
+            ])
+            .build::<Report>()
+    }
+
+    fn webhook(url: Url, template: &str) -> Webhook {
+        Webhook::new(url, template.to_string()).with_retry_policy(RetryPolicy {
+            max_retries: 1,
+            initial_backoff: Duration::ZERO,
+            max_backoff: Duration::ZERO,
+        })
+    }
+
+    #[test]
+    fn should_render_template_against_the_report() {
+        let body = webhook(Url::parse("http://localhost").unwrap(), TEMPLATE)
+            .render(&sample_report())
+            .expect("unable to render template");
+
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&body).expect("invalid JSON"),
+            json!({
+                "message": "Nightly \"e2e\": 1 failed",
+                "failures": ["a test failed", "a test passed"]
+            })
+        );
+    }
+
+    #[test]
+    fn should_fail_to_render_undefined_values() {
+        let error = webhook(
+            Url::parse("http://localhost").unwrap(),
+            "{{ summary.errored }}",
+        )
+        .render(&sample_report())
+        .expect_err("render should fail");
+
+        assert!(format!("{:#}", error).contains("undefined"));
+    }
+
+    #[test]
+    fn should_post_rendered_template_with_headers() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/hooks/incident")
+            .match_header("content-type", "application/json")
+            .match_header("x-api-key", "a-key")
+            .match_body(Matcher::PartialJson(json!({
+                "message": "Nightly \"e2e\": 1 failed"
+            })))
+            .with_status(202)
+            .create();
+
+        webhook(
+            Url::parse(&format!("{}/hooks/incident", server.url())).unwrap(),
+            TEMPLATE,
+        )
+        .with_header("X-Api-Key".to_string(), "a-key".to_string())
+        .send(&sample_report())
+        .expect("unable to send report");
+
+        mock.assert();
+    }
+
+    #[test]
+    fn should_retry_and_fail_on_server_errors() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/hook")
+            .match_query(Matcher::Any)
+            .with_status(502)
+            .with_body("bad gateway")
+            .expect(2)
+            .create();
+
+        let error = webhook(
+            Url::parse(&format!("{}/hook?token=secret", server.url())).unwrap(),
+            TEMPLATE,
+        )
+        .send(&sample_report())
+        .expect_err("send should fail");
+
+        mock.assert();
+        assert_eq!(
+            error.to_string(),
+            "Webhook on 127.0.0.1 responded with status 502: bad gateway"
+        );
+    }
+
+    #[test]
+    fn should_not_leak_the_webhook_url_when_unreachable() {
+        let error = webhook(
+            Url::parse("http://127.0.0.1:1/hooks/secret?token=secret").unwrap(),
+            TEMPLATE,
+        )
+        .send(&sample_report())
+        .expect_err("send should fail");
+
+        let error = format!("{:#}", error);
+        assert!(error.starts_with("Unable to reach webhook on 127.0.0.1"));
+        assert!(!error.contains("secret"));
+    }
+
+    #[test]
+    fn should_parse_headers() {
+        assert_eq!(
+            parse_header("Authorization: Bearer a:b").unwrap(),
+            ("Authorization".to_string(), "Bearer a:b".to_string())
+        );
+        assert!(parse_header("no separator").is_err());
+        assert!(parse_header(": no name").is_err());
+    }
+}